@@ -1,4 +1,5 @@
 use fragments_core::{
+    animation::Easing,
     components::{color, text},
     effect::StreamEffect,
     layout::{
@@ -57,13 +58,20 @@ struct GradientRect {
 
 impl Widget for GradientRect {
     fn mount(self, scope: &mut Scope) {
-        scope.set(size(), self.size);
+        scope.set(size(), Vec2::ZERO);
         scope.set_default(rectangle());
         scope.set(min_width(), self.size.x);
         scope.set(min_height(), self.size.y);
         scope.set_default(absolute_position());
         scope.set_default(local_position());
 
+        scope.animate(
+            size(),
+            self.size,
+            Duration::from_millis(400),
+            Easing::BackOut,
+        );
+
         let now = Instant::now();
         scope.on_global_event(move |scope, &RedrawEvent| {
             scope.set(
@@ -82,37 +90,6 @@ impl Widget for Text {
     }
 }
 
-struct Animated<F>(F);
-
-impl<F, W> Animated<F>
-where
-    F: 'static + FnMut(Duration) -> W,
-    W: Widget,
-{
-    fn new(f: F) -> Self {
-        Self(f)
-    }
-}
-
-impl<F, W> Widget for Animated<F>
-where
-    F: 'static + FnMut(Duration) -> W,
-    W: Widget,
-{
-    fn mount(self, scope: &mut Scope) {
-        let mut f = self.0;
-
-        let start = Instant::now();
-        let mut child = scope.attach(f(Duration::from_secs(0)));
-
-        scope.on_global_event(move |scope, &RedrawEvent| {
-            scope.detach(child);
-
-            child = scope.attach(f(start.elapsed()));
-        });
-    }
-}
-
 struct App {}
 
 impl Widget for App {
@@ -133,21 +110,15 @@ impl Widget for App {
 
         // scope.attach(count.signal().map(|v| Text(v.to_string())));
 
-        // scope.attach(Animated::new(move |t| Rect {
-        //     size: vec2(50.0, 50.0),
-        //     // pos: vec2(
-        //     //     400.0 + (t.as_secs_f32() * PI).sin() * 200.0,
-        //     //     300.0 + (t.as_secs_f32() * PI).cos() * 200.0,
-        //     // ),
-        // }));
-
-        // scope.attach(Animated::new(move |t| Rect {
-        //     size: vec2(100.0, 100.0),
-        //     // pos: vec2(
-        //     //     400.0 + t.as_secs_f32().sin() * 400.0,
-        //     //     200.0 + (t.as_secs_f32() * PI).cos() * 200.0,
-        //     // ),
-        // }));
+        // Grow the row, then shrink it back
+        scope
+            .animate(
+                size(),
+                vec2(800.0, 100.0),
+                Duration::from_secs(2),
+                Easing::CubicInOut,
+            )
+            .then(vec2(500.0, 100.0), Duration::from_secs(1), Easing::QuadOut);
 
         scope.create_effect(StreamEffect::new(
             interval(Duration::from_millis(200)).enumerate().take(16),
@@ -0,0 +1,94 @@
+use std::f32::consts::PI;
+
+/// Maps the linear progress of an animation to the eased progress.
+///
+/// Both the input and output are in `0.0..=1.0`, except for easings which overshoot such as
+/// [`Easing::BackOut`].
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Easing {
+    #[default]
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    SineInOut,
+    /// Overshoots the target slightly before settling
+    BackOut,
+    Custom(fn(f32) -> f32),
+}
+
+impl Easing {
+    /// Returns the eased progress for `t`, which is clamped to `0.0..=1.0`
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::QuadInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+                }
+            }
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Easing::SineInOut => -((PI * t).cos() - 1.0) / 2.0,
+            Easing::BackOut => {
+                const C1: f32 = 1.70158;
+                const C3: f32 = C1 + 1.0;
+                1.0 + C3 * (t - 1.0).powi(3) + C1 * (t - 1.0).powi(2)
+            }
+            Easing::Custom(func) => func(t),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn endpoints() {
+        let easings = [
+            Easing::Linear,
+            Easing::QuadIn,
+            Easing::QuadOut,
+            Easing::QuadInOut,
+            Easing::CubicIn,
+            Easing::CubicOut,
+            Easing::CubicInOut,
+            Easing::SineInOut,
+            Easing::BackOut,
+            Easing::Custom(|t| t.sqrt()),
+        ];
+
+        for easing in easings {
+            assert!(easing.apply(0.0).abs() < 1e-6, "{easing:?}");
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-6, "{easing:?}");
+            // Out of range progress is clamped
+            assert_eq!(easing.apply(2.0), easing.apply(1.0), "{easing:?}");
+        }
+    }
+
+    #[test]
+    fn shape() {
+        assert_eq!(Easing::Linear.apply(0.25), 0.25);
+        assert!(Easing::QuadIn.apply(0.25) < 0.25);
+        assert!(Easing::QuadOut.apply(0.25) > 0.25);
+        assert!((Easing::CubicInOut.apply(0.5) - 0.5).abs() < 1e-6);
+        assert!(Easing::BackOut.apply(0.8) > 1.0);
+    }
+}
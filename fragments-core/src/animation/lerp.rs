@@ -0,0 +1,95 @@
+use glam::{Vec2, Vec3, Vec4};
+use palette::{LinSrgba, Srgba};
+
+/// A value which can be linearly interpolated, allowing it to be animated
+pub trait Lerp: Clone {
+    /// Interpolates between `self` and `other`, where `t` of `0.0` is `self` and `1.0` is `other`.
+    ///
+    /// `t` may lie outside of the unit range for easings which overshoot.
+    fn lerp(&self, other: &Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Lerp for f64 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        self + (other - self) * t as f64
+    }
+}
+
+impl Lerp for Vec2 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Vec2::lerp(*self, *other, t)
+    }
+}
+
+impl Lerp for Vec3 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Vec3::lerp(*self, *other, t)
+    }
+}
+
+impl Lerp for Vec4 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Vec4::lerp(*self, *other, t)
+    }
+}
+
+/// Interpolates in linear space, as interpolating the gamma encoded components darkens the
+/// midpoint.
+impl Lerp for Srgba {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        let a: LinSrgba = self.into_linear();
+        let b: LinSrgba = other.into_linear();
+
+        Srgba::from_linear(LinSrgba::new(
+            a.red.lerp(&b.red, t),
+            a.green.lerp(&b.green, t),
+            a.blue.lerp(&b.blue, t),
+            a.alpha.lerp(&b.alpha, t),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use glam::vec2;
+
+    use super::*;
+
+    #[test]
+    fn lerp() {
+        assert_eq!(2.0.lerp(&4.0, 0.0), 2.0);
+        assert_eq!(2.0.lerp(&4.0, 0.5), 3.0);
+        assert_eq!(2.0.lerp(&4.0, 1.5), 5.0);
+
+        // Inherent methods of glam take precedence
+        assert_eq!(
+            Lerp::lerp(&vec2(0.0, 10.0), &vec2(10.0, 0.0), 0.25),
+            vec2(2.5, 7.5)
+        );
+    }
+
+    #[test]
+    fn lerp_color() {
+        let black = Srgba::new(0.0, 0.0, 0.0, 1.0);
+        let white = Srgba::new(1.0, 1.0, 1.0, 0.0);
+
+        let close = |a: Srgba, b: Srgba| {
+            let (a, b): ([f32; 4], [f32; 4]) = (a.into(), b.into());
+            a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-5)
+        };
+
+        assert!(close(black.lerp(&white, 0.0), black));
+        assert!(close(black.lerp(&white, 1.0), white));
+
+        let mid = black.lerp(&white, 0.5);
+        assert!((mid.alpha - 0.5).abs() < 1e-6);
+        // Linear light is brighter than the gamma encoded midpoint
+        assert!(mid.red > 0.7 && mid.red == mid.green && mid.green == mid.blue);
+    }
+}
@@ -0,0 +1,380 @@
+//! Tweening of component values and spring animated signals.
+//!
+//! See [`Scope::animate`] and [`Spring`].
+use std::{
+    collections::VecDeque,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use flax::{Component, ComponentValue, Entity};
+use parking_lot::Mutex;
+
+use crate::{
+    effect::TaskSpawner,
+    events::{EventHandler, EventRegistry, RedrawEvent},
+    frame::Frame,
+    time::TimersHandle,
+    Scope,
+};
+
+mod easing;
+mod lerp;
+mod spring;
+
+pub use easing::Easing;
+pub use lerp::Lerp;
+pub use spring::{Spring, SpringSignal, SpringValue};
+
+/// A single segment of an animation
+#[derive(Debug, Clone)]
+struct Tween<T> {
+    target: T,
+    duration: Duration,
+    easing: Easing,
+}
+
+struct AnimationState<T> {
+    queue: VecDeque<Tween<T>>,
+    /// The value and time the front tween started at
+    start: Option<(T, Instant)>,
+    /// Whether an animator is registered for the next frame
+    registered: bool,
+}
+
+impl<T: Lerp> AnimationState<T> {
+    /// Advances the animation to `now`, returning the new value if any tween is playing.
+    ///
+    /// `current` is used as the starting value if a tween begins this frame.
+    fn step(&mut self, now: Instant, current: impl FnOnce() -> Option<T>) -> Option<T> {
+        let mut current = Some(current);
+        let mut value = None;
+
+        while let Some(tween) = self.queue.front() {
+            let (from, start) = match self.start.take() {
+                Some(v) => v,
+                None => {
+                    let from = value
+                        .take()
+                        .or_else(|| current.take().and_then(|f| f()))
+                        .unwrap_or_else(|| tween.target.clone());
+
+                    (from, now)
+                }
+            };
+
+            let elapsed = now.saturating_duration_since(start);
+            if elapsed < tween.duration {
+                let t = elapsed.as_secs_f32() / tween.duration.as_secs_f32();
+                value = Some(from.lerp(&tween.target, tween.easing.apply(t)));
+                self.start = Some((from, start));
+                break;
+            }
+
+            let tween = self.queue.pop_front().unwrap();
+
+            // Start the next tween where this one ended, rather than at the next frame, to not
+            // drift when chaining
+            if !self.queue.is_empty() {
+                self.start = Some((tween.target.clone(), start + tween.duration));
+            }
+
+            value = Some(tween.target);
+        }
+
+        value
+    }
+}
+
+/// Stops an animation regardless of the animated type
+pub(crate) trait CancelAnimation: Send + Sync {
+    fn cancel(&self);
+}
+
+impl<T: Send> CancelAnimation for Mutex<AnimationState<T>> {
+    fn cancel(&self) {
+        let mut state = self.lock();
+        state.queue.clear();
+        state.start = None;
+    }
+}
+
+/// Steps an animation each frame
+struct Animator<T> {
+    id: Entity,
+    component: Component<T>,
+    timers: TimersHandle,
+    state: Arc<Mutex<AnimationState<T>>>,
+}
+
+impl<T> Clone for Animator<T> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            component: self.component,
+            timers: self.timers.clone(),
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T: ComponentValue + Lerp> EventHandler<RedrawEvent> for Animator<T> {
+    fn on_event(&mut self, frame: &mut Frame, _: &RedrawEvent) -> bool {
+        let mut state = self.state.lock();
+
+        let Some(mut scope) = Scope::try_from_id(frame, self.id) else {
+            state.queue.clear();
+            state.registered = false;
+            return false;
+        };
+
+        let now = self.timers.now();
+        let value = state.step(now, || {
+            let world = &scope.frame().world;
+            world.get(self.id, self.component).ok().map(|v| v.clone())
+        });
+
+        if let Some(value) = value {
+            scope.set(self.component, value);
+        }
+
+        if state.queue.is_empty() {
+            state.start = None;
+            state.registered = false;
+            false
+        } else {
+            scope.frame().spawner.request_update();
+            true
+        }
+    }
+}
+
+/// Controls an animation started by [`Scope::animate`].
+///
+/// Dropping the handle does not stop the animation.
+pub struct AnimationHandle<T> {
+    animator: Animator<T>,
+    events: Arc<EventRegistry>,
+    spawner: TaskSpawner<Frame>,
+}
+
+impl<T> Clone for AnimationHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            animator: self.animator.clone(),
+            events: self.events.clone(),
+            spawner: self.spawner.clone(),
+        }
+    }
+}
+
+impl<T: ComponentValue + Lerp> AnimationHandle<T> {
+    pub(crate) fn new(
+        frame: &Frame,
+        id: Entity,
+        component: Component<T>,
+        timers: TimersHandle,
+    ) -> Self {
+        Self {
+            animator: Animator {
+                id,
+                component,
+                timers,
+                state: Arc::new(Mutex::new(AnimationState {
+                    queue: VecDeque::new(),
+                    start: None,
+                    registered: false,
+                })),
+            },
+            events: frame.events.clone(),
+            spawner: frame.spawner.clone(),
+        }
+    }
+
+    /// Returns the state of the animation, for cancelling it when replaced
+    pub(crate) fn cancel_handle(&self) -> Arc<dyn CancelAnimation> {
+        self.animator.state.clone()
+    }
+
+    /// Queues a tween to `target` which starts once the previous tweens have finished.
+    ///
+    /// If the animation has already finished, the tween starts from the current value at the
+    /// next frame.
+    pub fn then(&self, target: T, duration: Duration, easing: Easing) -> &Self {
+        let mut state = self.animator.state.lock();
+
+        state.queue.push_back(Tween {
+            target,
+            duration,
+            easing,
+        });
+
+        if !state.registered {
+            state.registered = true;
+            self.events.register(Box::new(self.animator.clone()));
+            self.spawner.request_update();
+        }
+
+        self
+    }
+}
+
+impl<T: Send> AnimationHandle<T> {
+    /// Stops the current and all queued tweens, leaving the component at its current value
+    pub fn cancel(&self) {
+        self.animator.state.cancel();
+    }
+}
+
+impl<T> AnimationHandle<T> {
+    /// Returns true if all tweens have finished playing, or the animation was cancelled
+    pub fn is_finished(&self) -> bool {
+        self.animator.state.lock().queue.is_empty()
+    }
+}
+
+impl<T> std::fmt::Debug for AnimationHandle<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.animator.state.lock();
+        f.debug_struct("AnimationHandle")
+            .field("id", &self.animator.id)
+            .field("queued", &state.queue.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use flax::component;
+
+    use crate::{testing::TestApp, time, Widget};
+
+    use super::*;
+
+    component! {
+        value: f32,
+    }
+
+    struct Empty;
+
+    impl Widget for Empty {
+        fn mount(self, _: &mut Scope<'_>) {}
+    }
+
+    fn setup() -> (TestApp, Entity) {
+        let mut app = TestApp::new();
+        let id = app.mount(Empty);
+        app.world_mut().set(id, value(), 0.0).unwrap();
+        (app, id)
+    }
+
+    fn animate(app: &mut TestApp, id: Entity, target: f32, millis: u64) -> AnimationHandle<f32> {
+        let _guard = time::enter(app.timers());
+        Scope::try_from_id(app.frame_mut(), id).unwrap().animate(
+            value(),
+            target,
+            Duration::from_millis(millis),
+            Easing::Linear,
+        )
+    }
+
+    /// Advances the clock and steps the animations
+    fn frame(app: &mut TestApp, id: Entity, millis: u64) -> f32 {
+        app.advance(Duration::from_millis(millis));
+        app.emit(RedrawEvent);
+        app.get(id, value()).unwrap()
+    }
+
+    fn assert_near(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-4, "{a} != {b}");
+    }
+
+    #[test]
+    fn animate_step() {
+        let (mut app, id) = setup();
+        let handle = animate(&mut app, id, 1.0, 1000);
+
+        // Starts at the next frame
+        assert_near(frame(&mut app, id, 100), 0.0);
+        assert_near(frame(&mut app, id, 250), 0.25);
+        assert!(!handle.is_finished());
+
+        assert_near(frame(&mut app, id, 750), 1.0);
+        assert!(handle.is_finished());
+
+        // No longer animated
+        app.world_mut().set(id, value(), 5.0).unwrap();
+        assert_near(frame(&mut app, id, 100), 5.0);
+    }
+
+    #[test]
+    fn animate_then() {
+        let (mut app, id) = setup();
+        let handle = animate(&mut app, id, 1.0, 1000);
+        handle.then(0.0, Duration::from_secs(1), Easing::Linear);
+
+        assert_near(frame(&mut app, id, 0), 0.0);
+        assert_near(frame(&mut app, id, 500), 0.5);
+
+        // The second tween starts when the first ended rather than at the next frame
+        assert_near(frame(&mut app, id, 1000), 0.5);
+        assert!(!handle.is_finished());
+
+        assert_near(frame(&mut app, id, 500), 0.0);
+        assert!(handle.is_finished());
+
+        // Continues from the current value once finished
+        handle.then(2.0, Duration::from_secs(1), Easing::Linear);
+        assert_near(frame(&mut app, id, 0), 0.0);
+        assert_near(frame(&mut app, id, 500), 1.0);
+    }
+
+    #[test]
+    fn animate_cancel() {
+        let (mut app, id) = setup();
+        let handle = animate(&mut app, id, 1.0, 1000);
+
+        assert_near(frame(&mut app, id, 0), 0.0);
+        assert_near(frame(&mut app, id, 500), 0.5);
+
+        handle.cancel();
+        assert!(handle.is_finished());
+        assert_near(frame(&mut app, id, 500), 0.5);
+    }
+
+    #[test]
+    fn animate_replace() {
+        let (mut app, id) = setup();
+        let first = animate(&mut app, id, 1.0, 1000);
+
+        assert_near(frame(&mut app, id, 0), 0.0);
+        assert_near(frame(&mut app, id, 500), 0.5);
+
+        // Replaces the running animation, starting from the current value
+        let second = animate(&mut app, id, 0.0, 1000);
+        assert!(first.is_finished());
+
+        assert_near(frame(&mut app, id, 0), 0.5);
+        assert_near(frame(&mut app, id, 500), 0.25);
+        assert_near(frame(&mut app, id, 500), 0.0);
+        assert!(second.is_finished());
+    }
+
+    #[test]
+    fn animate_unmount() {
+        let (mut app, id) = setup();
+        let handle = animate(&mut app, id, 1.0, 1000);
+
+        assert_near(frame(&mut app, id, 0), 0.0);
+        assert_near(frame(&mut app, id, 500), 0.5);
+
+        app.world_mut().despawn(id).unwrap();
+        app.emit(RedrawEvent);
+        assert!(handle.is_finished());
+
+        // Chaining onto a stopped animation does not revive it
+        handle.then(0.0, Duration::from_secs(1), Easing::Linear);
+        app.emit(RedrawEvent);
+        assert!(handle.is_finished());
+    }
+}
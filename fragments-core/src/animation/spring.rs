@@ -0,0 +1,427 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use futures::Future;
+use glam::{Vec2, Vec3, Vec4};
+use palette::Srgba;
+use pin_project::pin_project;
+
+use crate::{
+    signal::Signal,
+    time::{current, Sleep, TimersHandle},
+};
+
+/// A value which can be simulated by a [`Spring`]
+pub trait SpringValue: Clone {
+    fn zero() -> Self;
+    /// Returns `self + other * scale`
+    fn add_scaled(&self, other: &Self, scale: f32) -> Self;
+    /// Returns the distance between two values, used to determine when a spring is at rest
+    fn distance(&self, other: &Self) -> f32;
+}
+
+impl SpringValue for f32 {
+    fn zero() -> Self {
+        0.0
+    }
+
+    fn add_scaled(&self, other: &Self, scale: f32) -> Self {
+        self + other * scale
+    }
+
+    fn distance(&self, other: &Self) -> f32 {
+        (self - other).abs()
+    }
+}
+
+macro_rules! impl_spring_value {
+    ($($ty: ty),*) => {
+        $(
+            impl SpringValue for $ty {
+                fn zero() -> Self {
+                    <$ty>::ZERO
+                }
+
+                fn add_scaled(&self, other: &Self, scale: f32) -> Self {
+                    *self + *other * scale
+                }
+
+                fn distance(&self, other: &Self) -> f32 {
+                    <$ty>::distance(*self, *other)
+                }
+            }
+        )*
+    };
+}
+
+impl_spring_value!(Vec2, Vec3, Vec4);
+
+/// Simulates each of the gamma encoded components
+impl SpringValue for Srgba {
+    fn zero() -> Self {
+        Srgba::new(0.0, 0.0, 0.0, 0.0)
+    }
+
+    fn add_scaled(&self, other: &Self, scale: f32) -> Self {
+        let a: [f32; 4] = (*self).into();
+        let b: [f32; 4] = (*other).into();
+        Vec4::from(a)
+            .add_scaled(&Vec4::from(b), scale)
+            .to_array()
+            .into()
+    }
+
+    fn distance(&self, other: &Self) -> f32 {
+        let a: [f32; 4] = (*self).into();
+        let b: [f32; 4] = (*other).into();
+        Vec4::from(a).distance(Vec4::from(b))
+    }
+}
+
+/// A damped spring which smoothly follows a changing target.
+///
+/// Unlike a tween, the velocity is kept when the target changes mid-flight, which makes springs
+/// suitable for direct manipulation such as dragging.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spring {
+    stiffness: f32,
+    damping: f32,
+    mass: f32,
+    precision: f32,
+    step: Duration,
+}
+
+impl Spring {
+    /// Creates a spring with unit mass.
+    ///
+    /// A higher `stiffness` pulls harder towards the target, and a higher `damping` reduces the
+    /// oscillation. A `damping` of `2 * stiffness.sqrt()` is critically damped, and settles as
+    /// fast as possible without overshooting.
+    pub fn new(stiffness: f32, damping: f32) -> Self {
+        Self {
+            stiffness,
+            damping,
+            mass: 1.0,
+            precision: 0.01,
+            step: Duration::from_micros(16_667),
+        }
+    }
+
+    pub fn with_mass(mut self, mass: f32) -> Self {
+        self.mass = mass;
+        self
+    }
+
+    /// Sets the distance and speed below which the spring snaps to the target and comes to rest
+    pub fn with_precision(mut self, precision: f32) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Sets the interval at which new values are yielded while moving. Defaults to 60 times per
+    /// second.
+    pub fn with_step(mut self, step: Duration) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Returns a signal which follows the values of `target`.
+    ///
+    /// The first value of `target` is yielded as is. The signal is closed once `target` is closed
+    /// and the spring has come to rest.
+    pub fn animate<S, T>(self, target: S) -> SpringSignal<S, T>
+    where
+        S: for<'x> Signal<'x, Item = T>,
+        T: SpringValue,
+    {
+        SpringSignal::new(self, target)
+    }
+
+    /// Advances the simulation by `dt`, using substeps of at most one step for stability
+    fn simulate<T: SpringValue>(&self, value: &mut T, velocity: &mut T, target: &T, dt: Duration) {
+        let mut remaining = dt;
+
+        while !remaining.is_zero() {
+            let dt = remaining.min(self.step);
+            remaining -= dt;
+
+            let displacement = target.add_scaled(value, -1.0);
+            let acceleration = T::zero()
+                .add_scaled(&displacement, self.stiffness / self.mass)
+                .add_scaled(velocity, -self.damping / self.mass);
+
+            // Semi implicit euler, which is stable for stiff springs at frame rate steps
+            *velocity = velocity.add_scaled(&acceleration, dt.as_secs_f32());
+            *value = value.add_scaled(velocity, dt.as_secs_f32());
+        }
+    }
+
+    fn is_at_rest<T: SpringValue>(&self, value: &T, velocity: &T, target: &T) -> bool {
+        value.distance(target) < self.precision && velocity.distance(&T::zero()) < self.precision
+    }
+}
+
+/// Animates the values of a signal using a [`Spring`].
+///
+/// See [`Spring::animate`]
+#[pin_project]
+pub struct SpringSignal<S, T> {
+    #[pin]
+    signal: S,
+    /// Fires at the next step while moving
+    #[pin]
+    sleep: Option<Sleep>,
+    /// Resolved when first polled, so that the timers of the polling executor are used rather
+    /// than those current where the signal was created
+    timers: Option<TimersHandle>,
+    spring: Spring,
+    target: Option<T>,
+    value: Option<T>,
+    velocity: T,
+    /// The time the simulation was last advanced to while moving
+    last: Option<Instant>,
+    is_closed: bool,
+}
+
+impl<S, T: SpringValue> SpringSignal<S, T> {
+    fn new(spring: Spring, signal: S) -> Self {
+        Self {
+            signal,
+            sleep: None,
+            timers: None,
+            spring,
+            target: None,
+            value: None,
+            velocity: T::zero(),
+            last: None,
+            is_closed: false,
+        }
+    }
+}
+
+impl<'a, S, T> Signal<'a> for SpringSignal<S, T>
+where
+    S: for<'x> Signal<'x, Item = T>,
+    T: 'a + SpringValue,
+{
+    type Item = T;
+
+    fn poll_changed(self: Pin<&'a mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut p = self.project();
+        let timers = p.timers.get_or_insert_with(current);
+
+        while !*p.is_closed {
+            match p.signal.as_mut().poll_changed(cx) {
+                Poll::Ready(Some(v)) => {
+                    if p.value.is_none() {
+                        *p.value = Some(v.clone());
+                        *p.target = Some(v.clone());
+                        return Poll::Ready(Some(v));
+                    }
+
+                    if p.last.is_none() {
+                        // Start simulating from now rather than from when the spring came to rest
+                        let now = timers.now();
+                        *p.last = Some(now);
+                        p.sleep.set(Some(timers.sleep_until(now + p.spring.step)));
+                    }
+
+                    *p.target = Some(v);
+                }
+                Poll::Ready(None) => *p.is_closed = true,
+                Poll::Pending => break,
+            }
+        }
+
+        // At rest, no timer is kept alive until the target changes
+        let (Some(value), Some(target), Some(last), Some(mut sleep)) = (
+            p.value.as_mut(),
+            p.target.as_ref(),
+            p.last.as_mut(),
+            p.sleep.as_mut().as_pin_mut(),
+        ) else {
+            return if *p.is_closed {
+                Poll::Ready(None)
+            } else {
+                Poll::Pending
+            };
+        };
+
+        if sleep.as_mut().poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+
+        let now = timers.now();
+
+        p.spring.simulate(
+            value,
+            p.velocity,
+            target,
+            now.saturating_duration_since(*last),
+        );
+
+        if p.spring.is_at_rest(value, p.velocity, target) {
+            *value = target.clone();
+            *p.velocity = T::zero();
+            *p.last = None;
+            p.sleep.set(None);
+        } else {
+            *last = now;
+            sleep.reset(now + p.spring.step);
+        }
+
+        Poll::Ready(Some(value.clone()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use futures::FutureExt;
+
+    use crate::{
+        signal::Mutable,
+        time::{enter, MockClock},
+    };
+
+    use super::*;
+
+    const STEP: Duration = Duration::from_micros(16_667);
+
+    /// Steps the clock until the spring comes to rest, returning the yielded values
+    fn run<S>(clock: &MockClock, signal: &mut S) -> Vec<f32>
+    where
+        S: Unpin + for<'x> Signal<'x, Item = f32>,
+    {
+        let mut values = Vec::new();
+
+        for _ in 0..1000 {
+            clock.advance(STEP);
+            match signal.next_value().now_or_never() {
+                Some(Some(v)) => values.push(v),
+                // Closed, or at rest
+                Some(None) | None => break,
+            }
+        }
+
+        values
+    }
+
+    #[test]
+    fn spring() {
+        let clock = MockClock::new();
+        let _guard = enter(clock.handle());
+
+        let target = Mutable::new(0.0);
+        let mut signal = Spring::new(170.0, 26.0).animate(target.signal());
+
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(0.0)));
+        assert_eq!(signal.next_value().now_or_never(), None);
+
+        // Starts moving at the next step
+        target.set(10.0);
+        assert_eq!(signal.next_value().now_or_never(), None);
+        let values = run(&clock, &mut signal);
+
+        assert!(values.len() > 10, "{values:?}");
+        assert!(values.windows(2).all(|v| v[0] < v[1]), "{values:?}");
+        assert_eq!(values.last(), Some(&10.0));
+
+        // At rest
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(signal.next_value().now_or_never(), None);
+    }
+
+    #[test]
+    fn spring_retarget() {
+        let clock = MockClock::new();
+        let _guard = enter(clock.handle());
+
+        let target = Mutable::new(0.0);
+        let mut signal = Spring::new(170.0, 26.0).animate(target.signal());
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(0.0)));
+
+        target.set(-10.0);
+        assert_eq!(signal.next_value().now_or_never(), None);
+        let mut last = 0.0;
+        for _ in 0..5 {
+            clock.advance(STEP);
+            last = signal.next_value().now_or_never().unwrap().unwrap();
+        }
+        assert!(last < -1.0);
+
+        // Compare against a spring which starts at rest from the same value
+        let rest_target = Mutable::new(last);
+        let mut rest = Spring::new(170.0, 26.0).animate(rest_target.signal());
+        assert_eq!(rest.next_value().now_or_never(), Some(Some(last)));
+
+        target.set(10.0);
+        rest_target.set(10.0);
+        assert_eq!(rest.next_value().now_or_never(), None);
+
+        // The velocity is kept, so the spring is slower to turn around
+        clock.advance(STEP);
+        let next = signal.next_value().now_or_never().unwrap().unwrap();
+        let next_rest = rest.next_value().now_or_never().unwrap().unwrap();
+        assert!(next - last < next_rest - last, "{last} {next} {next_rest}");
+
+        let values = run(&clock, &mut signal);
+        assert_eq!(values.last(), Some(&10.0));
+
+        // Settles after the target is closed
+        target.set(0.0);
+        assert_eq!(signal.next_value().now_or_never(), None);
+        drop(target);
+        let values = run(&clock, &mut signal);
+        assert_eq!(values.last(), Some(&0.0));
+        assert_eq!(signal.next_value().now_or_never(), Some(None));
+    }
+
+    #[test]
+    fn spring_lazy_timers() {
+        let target = Mutable::new(0.0);
+        let mut signal = Spring::new(170.0, 26.0).animate(target.signal());
+
+        // The timers are resolved when polled rather than when created
+        let clock = MockClock::new();
+        let _guard = enter(clock.handle());
+
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(0.0)));
+
+        target.set(10.0);
+        assert_eq!(signal.next_value().now_or_never(), None);
+        let values = run(&clock, &mut signal);
+
+        assert!(values.len() > 10, "{values:?}");
+        assert_eq!(values.last(), Some(&10.0));
+    }
+
+    #[test]
+    fn spring_vec() {
+        let clock = MockClock::new();
+        let _guard = enter(clock.handle());
+
+        let target = Mutable::new(Vec2::ZERO);
+        let mut signal = Spring::new(300.0, 10.0).animate(target.signal());
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(Vec2::ZERO)));
+
+        target.set(Vec2::ONE);
+        assert_eq!(signal.next_value().now_or_never(), None);
+
+        let mut overshot = false;
+        let mut last = None;
+        while let Some(Some(v)) = {
+            clock.advance(STEP);
+            signal.next_value().now_or_never()
+        } {
+            // Underdamped springs overshoot
+            overshot |= v.x > 1.0;
+            last = Some(v);
+        }
+
+        assert!(overshot);
+        assert_eq!(last, Some(Vec2::ONE));
+    }
+}
@@ -1,7 +1,9 @@
 use flax::*;
+use std::{collections::HashMap, sync::Arc};
+
 use palette::Srgba;
 
-use crate::effect::TaskHandle;
+use crate::{animation::CancelAnimation, effect::TaskHandle, time::TimersHandle};
 
 #[derive(Default)]
 pub(crate) struct OnCleanup(Vec<Box<dyn FnOnce() + Send + Sync>>);
@@ -29,6 +31,8 @@ component! {
     pub(crate) tasks: Vec<TaskHandle>,
     pub(crate) ordered_children: Vec<Entity> => [ Debuggable ],
     pub(crate) on_cleanup: OnCleanup => [ Debuggable ],
+    /// The latest animation of each animated component, which is replaced by the next
+    pub(crate) animations: HashMap<ComponentKey, Arc<dyn CancelAnimation>>,
     /// Runs when a widget is unmounted/detached
 
     pub text: String => [ Debuggable ],
@@ -37,3 +41,8 @@ component! {
 
     pub resources,
 }
+
+crate::context! {
+    /// The timers used by the effects of a widget and its descendants
+    pub(crate) timers: TimersHandle,
+}
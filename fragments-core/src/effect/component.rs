@@ -0,0 +1,59 @@
+use flax::{filter::ChangeFilter, Component, ComponentValue, Entity, Query, World};
+
+use crate::{frame::WorldObserver, signal::Mutable};
+
+/// Writes the value of a component into a [`Mutable`] each time it is modified.
+///
+/// Changes are detected using flax change tracking when the frame observes the world.
+pub(crate) struct ComponentObserver<T: ComponentValue> {
+    /// The widget which observes the component
+    owner: Entity,
+    id: Entity,
+    component: Component<T>,
+    query: Query<ChangeFilter<T>>,
+    value: Mutable<Option<T>>,
+}
+
+impl<T: ComponentValue> ComponentObserver<T> {
+    /// `query` is expected to already have observed the current value of `value`
+    pub fn new(
+        owner: Entity,
+        id: Entity,
+        component: Component<T>,
+        query: Query<ChangeFilter<T>>,
+        value: Mutable<Option<T>>,
+    ) -> Self {
+        Self {
+            owner,
+            id,
+            component,
+            query,
+            value,
+        }
+    }
+}
+
+impl<T> WorldObserver for ComponentObserver<T>
+where
+    T: ComponentValue + Clone,
+{
+    fn observe(&mut self, world: &World) -> bool {
+        if !world.is_alive(self.owner) {
+            tracing::debug!(owner = ?self.owner, "Observing widget was unmounted");
+            return false;
+        }
+
+        if !world.is_alive(self.id) {
+            tracing::debug!(id = ?self.id, "Observed entity was despawned");
+            return false;
+        }
+
+        if let Ok(value) = self.query.borrow(world).get(self.id) {
+            self.value.set(Some(value.clone()));
+        } else if !world.has(self.id, self.component) && self.value.read().is_some() {
+            self.value.set(None);
+        }
+
+        true
+    }
+}
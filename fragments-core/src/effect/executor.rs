@@ -1,52 +1,152 @@
 use std::{
+    any::Any,
     mem,
+    ops::AddAssign,
+    panic::{self, AssertUnwindSafe},
     pin::Pin,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc, Weak,
     },
     task::{Context, Poll, Waker},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
-use futures::task::{waker_ref, ArcWake};
+use futures::{
+    ready,
+    task::{waker_ref, ArcWake},
+    Future,
+};
 use parking_lot::Mutex;
+use pin_project::pin_project;
 use slotmap::new_key_type;
+use thiserror::Error;
+
+use crate::time::{self, TimersHandle};
 
 use super::Effect;
 
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum TaskError {
+    #[error("The task was aborted")]
+    Aborted,
+    #[error("The task panicked: {0}")]
+    Panicked(String),
+}
+
+enum TaskStatus {
+    Running,
+    Finished,
+    Failed(TaskError),
+}
+
+struct TaskState {
+    status: TaskStatus,
+    /// Woken when the task completes
+    join_wakers: Vec<Waker>,
+    /// Wakes the task itself, used to promptly remove aborted tasks
+    task_waker: Option<Waker>,
+}
+
 struct SharedTaskData {
     aborted: AtomicBool,
+    state: Mutex<TaskState>,
+}
+
+impl SharedTaskData {
+    fn complete(&self, status: TaskStatus) {
+        let mut state = self.state.lock();
+        if let TaskStatus::Running = state.status {
+            state.status = status;
+            state.task_waker = None;
+            state.join_wakers.drain(..).for_each(|v| v.wake());
+        }
+    }
 }
 
 /// Represents a handle to a running task.
-pub struct TaskHandle {
-    shared: Weak<SharedTaskData>,
+///
+/// Awaiting the handle yields the output of the task once it completes. Dropping the handle
+/// detaches the task.
+pub struct TaskHandle<O = ()> {
+    shared: Arc<SharedTaskData>,
+    output: Arc<Mutex<Option<O>>>,
 }
 
-impl TaskHandle {
-    pub fn abort_on_drop(self) -> AbortTaskHandle {
+impl<O> TaskHandle<O> {
+    pub fn abort_on_drop(self) -> AbortTaskHandle<O> {
         AbortTaskHandle(self)
     }
 
     /// Aborts the task remotely
     pub fn abort(&self) {
-        if let Some(shared) = self.shared.upgrade() {
-            shared.aborted.store(true, Ordering::SeqCst)
+        self.shared.aborted.store(true, Ordering::SeqCst);
+
+        if let Some(waker) = self.shared.state.lock().task_waker.take() {
+            waker.wake()
+        }
+    }
+
+    /// Returns true if the task has completed, was aborted, or panicked
+    pub fn is_finished(&self) -> bool {
+        !matches!(self.shared.state.lock().status, TaskStatus::Running)
+    }
+
+    /// Returns the panic message if the task panicked
+    pub fn panic_message(&self) -> Option<String> {
+        match &self.shared.state.lock().status {
+            TaskStatus::Failed(TaskError::Panicked(message)) => Some(message.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl<O> Future for TaskHandle<O> {
+    type Output = Result<O, TaskError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.shared.state.lock();
+
+        match &state.status {
+            TaskStatus::Running => {
+                if !state.join_wakers.iter().any(|v| v.will_wake(cx.waker())) {
+                    state.join_wakers.push(cx.waker().clone());
+                }
+
+                Poll::Pending
+            }
+            TaskStatus::Finished => Poll::Ready(Ok(self
+                .output
+                .lock()
+                .take()
+                .expect("TaskHandle polled after completion"))),
+            TaskStatus::Failed(err) => Poll::Ready(Err(err.clone())),
         }
     }
 }
 
+impl<O> std::fmt::Debug for TaskHandle<O> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskHandle")
+            .field("is_finished", &self.is_finished())
+            .finish()
+    }
+}
+
 /// Variant of a task handle which aborts the task when dropped
-pub struct AbortTaskHandle(TaskHandle);
+pub struct AbortTaskHandle<O = ()>(TaskHandle<O>);
 
-impl AbortTaskHandle {
-    fn abort(&self) {
-        self.abort()
+impl<O> AbortTaskHandle<O> {
+    pub fn abort(&self) {
+        self.0.abort()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.0.is_finished()
     }
 }
 
-impl Drop for AbortTaskHandle {
+impl<O> Drop for AbortTaskHandle<O> {
     fn drop(&mut self) {
         self.abort()
     }
@@ -76,40 +176,157 @@ impl ArcWake for TaskWaker {
     }
 }
 
+/// The point in a frame at which a task is polled.
+///
+/// See [`Executor::update_phase`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Phase {
+    /// Before the layout is updated. Effects which modify the widget tree should run here.
+    #[default]
+    PreLayout,
+    /// After the layout is updated, effects can observe the layout of the current frame
+    PostLayout,
+    /// Right before the frame is rendered
+    PreRender,
+    /// After the frame is rendered, if there is frame time remaining
+    Idle,
+}
+
+impl Phase {
+    /// All phases in the order they run in a frame
+    pub const ALL: [Phase; 4] = [
+        Phase::PreLayout,
+        Phase::PostLayout,
+        Phase::PreRender,
+        Phase::Idle,
+    ];
+}
+
+/// Reports the work done by an executor update
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct UpdateStats {
+    /// Number of tasks polled
+    pub polled: usize,
+    /// Number of ready tasks which were carried over to the next update as the budget ran out
+    pub deferred: usize,
+}
+
+impl AddAssign for UpdateStats {
+    fn add_assign(&mut self, rhs: Self) {
+        self.polled += rhs.polled;
+        self.deferred += rhs.deferred;
+    }
+}
+
+/// Invoked with the state and the panic message when a task panics
+pub type PanicHandler<T> = Box<dyn FnOnce(&mut T, &str)>;
+
 /// Represents a unit of effect execution which runs using `C`
 pub(crate) struct Task<T> {
     effect: Pin<Box<dyn Effect<T>>>,
+    phase: Phase,
+    on_panic: Option<PanicHandler<T>>,
 
     shared: Arc<SharedTaskData>,
 }
 
 impl<T> Task<T> {
-    pub(crate) fn new(effect: Pin<Box<dyn Effect<T>>>) -> (Task<T>, TaskHandle) {
+    /// Creates a new task, where `output` is taken by the handle once the task has finished
+    pub(crate) fn new<O>(
+        effect: Pin<Box<dyn Effect<T>>>,
+        output: Arc<Mutex<Option<O>>>,
+    ) -> (Task<T>, TaskHandle<O>) {
         let shared = Arc::new(SharedTaskData {
             aborted: AtomicBool::new(false),
+            state: Mutex::new(TaskState {
+                status: TaskStatus::Running,
+                join_wakers: Vec::new(),
+                task_waker: None,
+            }),
         });
 
         let handle = TaskHandle {
-            shared: Arc::downgrade(&shared),
+            shared: shared.clone(),
+            output,
         };
 
-        let task = Self { effect, shared };
+        let task = Self {
+            effect,
+            phase: Phase::default(),
+            on_panic: None,
+            shared,
+        };
 
         (task, handle)
     }
 
-    fn update(&mut self, waker: &Arc<TaskWaker>, state: &mut T) -> Poll<()> {
+    /// Polls the task.
+    ///
+    /// Returns the panic message if the effect panicked, in which case the task is finished.
+    fn update(&mut self, waker: &Arc<TaskWaker>, state: &mut T) -> Result<Poll<()>, String> {
         if self.shared.aborted.load(Ordering::Relaxed) {
             tracing::info!("Task aborted remotely");
-            return Poll::Ready(());
+            self.shared.complete(TaskStatus::Failed(TaskError::Aborted));
+            return Ok(Poll::Ready(()));
         }
 
         let waker = waker_ref(waker);
+        self.shared.state.lock().task_waker = Some((*waker).clone());
         let mut cx = Context::from_waker(&waker);
 
         let effect = self.effect.as_mut();
 
-        effect.poll_effect(state, &mut cx)
+        let result = panic::catch_unwind(AssertUnwindSafe(|| effect.poll_effect(state, &mut cx)));
+        let poll = match result {
+            Ok(poll) => poll,
+            Err(payload) => {
+                let message = panic_message(&*payload);
+                self.shared
+                    .complete(TaskStatus::Failed(TaskError::Panicked(message.clone())));
+
+                return Err(message);
+            }
+        };
+
+        if poll.is_ready() {
+            self.shared.complete(TaskStatus::Finished);
+        }
+
+        Ok(poll)
+    }
+}
+
+impl<T> Drop for Task<T> {
+    fn drop(&mut self) {
+        // The executor was dropped before the task completed
+        self.shared.complete(TaskStatus::Failed(TaskError::Aborted));
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Task panicked".into()
+    }
+}
+
+/// Stores the output of a future into the output slot of the task
+#[pin_project]
+struct OutputEffect<Fut: Future> {
+    #[pin]
+    fut: Fut,
+    output: Arc<Mutex<Option<Fut::Output>>>,
+}
+
+impl<T, Fut: Future> Effect<T> for OutputEffect<Fut> {
+    fn poll_effect(self: Pin<&mut Self>, _: &mut T, cx: &mut Context<'_>) -> Poll<()> {
+        let p = self.project();
+        let output = ready!(p.fut.poll(cx));
+        *p.output.lock() = Some(output);
+        Poll::Ready(())
     }
 }
 
@@ -143,6 +360,10 @@ pub struct Executor<T> {
     tasks: slotmap::SlotMap<TaskKey, (Task<T>, Arc<TaskWaker>)>,
     new_tasks: Arc<Mutex<Vec<Task<T>>>>,
     processing: Vec<TaskKey>,
+    /// Ready tasks waiting for their phase
+    queued: [Vec<TaskKey>; Phase::ALL.len()],
+    /// Timers used by tasks, unless overridden by the task itself
+    timers: Option<TimersHandle>,
     shared: Arc<Shared>,
 }
 
@@ -158,6 +379,8 @@ impl<T> Executor<T> {
             tasks: Default::default(),
             new_tasks: Default::default(),
             processing: Default::default(),
+            queued: Default::default(),
+            timers: None,
             shared,
         }
     }
@@ -178,6 +401,21 @@ impl<T> Executor<T> {
         }
     }
 
+    /// Sets the waker which is woken when tasks become ready to update.
+    ///
+    /// Allows an event loop to sleep until there is work to do.
+    pub fn set_waker(&mut self, waker: Waker) {
+        *self.shared.waker.lock() = Some(waker);
+    }
+
+    /// Sets the timers used by the tasks, such as a [`MockClock`](crate::time::MockClock) for
+    /// testing.
+    ///
+    /// Defaults to the global timers.
+    pub fn set_timers(&mut self, timers: TimersHandle) {
+        self.timers = Some(timers);
+    }
+
     pub fn spawner(&self) -> TaskSpawner<T> {
         TaskSpawner {
             shared: Arc::downgrade(&self.shared),
@@ -185,8 +423,150 @@ impl<T> Executor<T> {
         }
     }
 
-    /// Updates the executor, polling ready tasks using the provided state
+    /// Updates the executor, polling ready tasks of all phases in order using the provided state
     pub fn update(&mut self, state: &mut T) {
+        for phase in Phase::ALL {
+            self.update_phase(phase, state);
+        }
+    }
+
+    /// Polls the ready tasks of `phase`.
+    ///
+    /// Tasks of other phases stay queued until their phase is updated.
+    pub fn update_phase(&mut self, phase: Phase, state: &mut T) {
+        self.poll_phase(phase, None, state);
+    }
+
+    /// Updates all phases in order, stopping when `budget` has elapsed.
+    ///
+    /// Tasks which did not get to run are carried over to the next update, ahead of tasks which
+    /// become ready later. At least one task is polled for each phase, so that no phase starves.
+    pub fn update_with_budget(&mut self, budget: Duration, state: &mut T) -> UpdateStats {
+        let deadline = self.now() + budget;
+
+        let mut stats = UpdateStats::default();
+        for phase in Phase::ALL {
+            stats += self.update_phase_until(phase, deadline, state);
+        }
+
+        stats
+    }
+
+    /// Polls the ready tasks of `phase` until `deadline`, as measured by the timers of the
+    /// executor.
+    ///
+    /// See [`Self::update_with_budget`]
+    pub fn update_phase_until(
+        &mut self,
+        phase: Phase,
+        deadline: Instant,
+        state: &mut T,
+    ) -> UpdateStats {
+        self.poll_phase(phase, Some(deadline), state)
+    }
+
+    /// Skips `phase` for this update, carrying its ready tasks over to the next update.
+    ///
+    /// The skipped tasks are reported as deferred, and another update is requested for them.
+    pub fn skip_phase(&mut self, phase: Phase) -> UpdateStats {
+        self.collect_ready();
+
+        let deferred = self.queued[phase as usize].len();
+        if deferred > 0 {
+            tracing::debug!(?phase, deferred, "Skipped phase");
+            self.shared.wake();
+        }
+
+        UpdateStats {
+            polled: 0,
+            deferred,
+        }
+    }
+
+    /// Returns the current time of the timers, which the update budget is measured in
+    fn now(&self) -> Instant {
+        self.timers
+            .as_ref()
+            .map_or_else(Instant::now, |timers| timers.now())
+    }
+
+    fn poll_phase(
+        &mut self,
+        phase: Phase,
+        deadline: Option<Instant>,
+        state: &mut T,
+    ) -> UpdateStats {
+        self.collect_ready();
+
+        let mut processing = mem::take(&mut self.queued[phase as usize]);
+        let mut stats = UpdateStats::default();
+
+        let _guard = self.timers.clone().map(time::enter);
+
+        for (i, &key) in processing.iter().enumerate() {
+            if stats.polled > 0 && deadline.is_some_and(|deadline| self.now() >= deadline) {
+                // Run the remaining tasks first next update
+                let deferred = &processing[i..];
+                stats.deferred = deferred.len();
+                self.queued[phase as usize].extend_from_slice(deferred);
+                tracing::debug!(?phase, deferred = stats.deferred, "Update budget exceeded");
+                break;
+            }
+
+            let Some((task, waker)) = self.tasks.get_mut(key) else {
+                tracing::warn!("No such task");
+                continue; };
+
+            stats.polled += 1;
+
+            // Reset the waker so that it is ready to use again
+            waker.sent.store(false, Ordering::SeqCst);
+
+            // Poll the task, removing the task if ready
+            match task.update(waker, state) {
+                Ok(Poll::Ready(())) => {
+                    self.tasks.remove(key).unwrap();
+                }
+                Ok(Poll::Pending) => {}
+                Err(message) => {
+                    // Isolate the panic to the task rather than unwinding through the event loop
+                    tracing::error!("Task panicked: {message}");
+                    let (mut task, _) = self.tasks.remove(key).unwrap();
+
+                    if let Some(on_panic) = task.on_panic.take() {
+                        on_panic(state, &message);
+                    }
+                }
+            }
+        }
+
+        // Reuse the allocation
+        processing.clear();
+        self.processing = processing;
+
+        if stats.deferred > 0 {
+            // Request another update for the deferred tasks, as the event loop may otherwise
+            // sleep until something unrelated wakes it
+            self.shared.wake();
+        } else if self.queued.iter().any(|v| !v.is_empty()) {
+            // Tasks queued for a later phase are picked up by `poll_update`
+            self.shared.has_updates.store(true, Ordering::SeqCst);
+        }
+
+        stats
+    }
+
+    /// Returns true if no task is ready to be polled.
+    ///
+    /// Pending tasks are not considered, as they are waiting to be woken by something else.
+    pub fn is_idle(&self) -> bool {
+        self.queued.iter().all(|v| v.is_empty())
+            && self.shared.ready.lock().is_empty()
+            && self.new_tasks.lock().is_empty()
+    }
+
+    /// Moves woken and new tasks to the queue of their phase
+    fn collect_ready(&mut self) {
         {
             let mut ready = self.shared.ready.lock();
             self.shared.has_updates.store(false, Ordering::SeqCst);
@@ -211,16 +591,9 @@ impl<T> Executor<T> {
         }
 
         for key in self.processing.drain(..) {
-            let Some((task, waker)) = self.tasks.get_mut(key) else {
-                tracing::warn!("No such task");
-                continue; };
-
-            // Reset the waker so that it is ready to use again
-            waker.sent.store(false, Ordering::SeqCst);
-
-            // Poll the task, removing the task if ready
-            if task.update(waker, state).is_ready() {
-                self.tasks.remove(key).unwrap();
+            match self.tasks.get(key) {
+                Some((task, _)) => self.queued[task.phase as usize].push(key),
+                None => tracing::warn!("No such task"),
             }
         }
     }
@@ -242,6 +615,16 @@ impl<T> Clone for TaskSpawner<T> {
 }
 
 impl<T> TaskSpawner<T> {
+    /// Wakes the executor without any task being ready.
+    ///
+    /// Used by per frame work outside of tasks, such as animations, to keep a backend which sleeps
+    /// when idle producing frames.
+    pub fn request_update(&self) {
+        if let Some(shared) = self.shared.upgrade() {
+            shared.wake();
+        }
+    }
+
     /// Spawns a new task.
     pub fn spawn<E>(&self, effect: E) -> TaskHandle
     where
@@ -251,10 +634,88 @@ impl<T> TaskSpawner<T> {
     }
 
     pub fn spawn_boxed(&self, effect: Pin<Box<dyn Effect<T>>>) -> TaskHandle {
+        self.spawn_inner(
+            effect,
+            Arc::new(Mutex::new(Some(()))),
+            Phase::default(),
+            None,
+        )
+    }
+
+    /// Spawns a new task, invoking `on_panic` if the effect panics.
+    ///
+    /// A panicking task is removed from the executor without affecting other tasks.
+    pub fn spawn_with_panic_handler<E>(
+        &self,
+        effect: E,
+        on_panic: impl 'static + FnOnce(&mut T, &str),
+    ) -> TaskHandle
+    where
+        E: 'static + Effect<T>,
+    {
+        self.spawn_task(Phase::default(), effect, Some(Box::new(on_panic)))
+    }
+
+    /// Spawns a new task which is polled in `phase`
+    pub fn spawn_in_phase<E>(&self, phase: Phase, effect: E) -> TaskHandle
+    where
+        E: 'static + Effect<T>,
+    {
+        self.spawn_task(phase, effect, None)
+    }
+
+    pub(crate) fn spawn_task<E>(
+        &self,
+        phase: Phase,
+        effect: E,
+        on_panic: Option<PanicHandler<T>>,
+    ) -> TaskHandle
+    where
+        E: 'static + Effect<T>,
+    {
+        self.spawn_inner(
+            Box::pin(effect),
+            Arc::new(Mutex::new(Some(()))),
+            phase,
+            on_panic,
+        )
+    }
+
+    /// Spawns a future as a new task.
+    ///
+    /// The returned handle can be awaited, for example from another task, to retrieve the output
+    /// of the future.
+    pub fn spawn_future<Fut>(&self, fut: Fut) -> TaskHandle<Fut::Output>
+    where
+        Fut: 'static + Future,
+        Fut::Output: 'static,
+    {
+        let output = Arc::new(Mutex::new(None));
+
+        self.spawn_inner(
+            Box::pin(OutputEffect {
+                fut,
+                output: output.clone(),
+            }),
+            output,
+            Phase::default(),
+            None,
+        )
+    }
+
+    fn spawn_inner<O>(
+        &self,
+        effect: Pin<Box<dyn Effect<T>>>,
+        output: Arc<Mutex<Option<O>>>,
+        phase: Phase,
+        on_panic: Option<PanicHandler<T>>,
+    ) -> TaskHandle<O> {
         let shared = self.shared.upgrade().expect("No executor running");
         let new_tasks = self.new_tasks.upgrade().expect("No executor running");
 
-        let (task, handle) = Task::new(effect);
+        let (mut task, handle) = Task::new(effect, output);
+        task.phase = phase;
+        task.on_panic = on_panic;
 
         new_tasks.lock().push(task);
         shared.wake();
@@ -262,3 +723,279 @@ impl<T> TaskSpawner<T> {
         handle
     }
 }
+
+#[cfg(test)]
+mod test {
+    use futures::{channel::oneshot, task::noop_waker, FutureExt};
+
+    use crate::{effect::FnOnceEffect, time::MockClock};
+
+    use super::*;
+
+    #[test]
+    fn join() {
+        let mut executor = Executor::<i32>::new();
+        let spawner = executor.spawner();
+
+        let (tx, rx) = oneshot::channel();
+        let mut handle = spawner.spawn_future(async move { rx.await.unwrap() * 2 });
+
+        executor.update(&mut 0);
+        assert!(!handle.is_finished());
+        assert_eq!((&mut handle).now_or_never(), None);
+
+        tx.send(21).unwrap();
+        executor.update(&mut 0);
+        assert!(handle.is_finished());
+        assert_eq!(handle.now_or_never(), Some(Ok(42)));
+
+        let handle = spawner.spawn(FnOnceEffect::new(|v: &mut i32| *v += 1));
+        let mut state = 0;
+        executor.update(&mut state);
+        assert_eq!(state, 1);
+        assert_eq!(handle.now_or_never(), Some(Ok(())));
+    }
+
+    #[test]
+    fn abort() {
+        let mut executor = Executor::<()>::new();
+        let spawner = executor.spawner();
+
+        let handle = spawner.spawn_future(futures::future::pending::<()>());
+        executor.update(&mut ());
+
+        // Aborting wakes the task so that it is removed next update
+        handle.abort();
+        assert!(executor
+            .poll_update(Context::from_waker(&noop_waker()), &mut ())
+            .is_ready());
+
+        assert!(handle.is_finished());
+        assert_eq!(handle.now_or_never(), Some(Err(TaskError::Aborted)));
+
+        let handle = spawner
+            .spawn_future(futures::future::pending::<()>())
+            .abort_on_drop();
+        executor.update(&mut ());
+        assert!(!handle.is_finished());
+        drop(handle);
+        executor.update(&mut ());
+        assert!(executor.tasks.is_empty());
+    }
+
+    #[test]
+    fn panicked() {
+        let mut executor = Executor::<Vec<String>>::new();
+        let spawner = executor.spawner();
+
+        let handle = spawner.spawn_future(async { panic!("Effect failed") });
+        let (tx, rx) = oneshot::channel();
+        let other = spawner.spawn_future(async move { rx.await.unwrap() });
+
+        let panicking = spawner.spawn_with_panic_handler(
+            FnOnceEffect::new(|_: &mut Vec<String>| panic!("Handled failure")),
+            |panics: &mut Vec<String>, message: &str| panics.push(message.into()),
+        );
+
+        let mut panics = Vec::new();
+        executor.update(&mut panics);
+
+        assert!(handle.is_finished());
+        assert_eq!(handle.panic_message().as_deref(), Some("Effect failed"));
+        assert_eq!(
+            panicking.now_or_never(),
+            Some(Err(TaskError::Panicked("Handled failure".into())))
+        );
+        assert_eq!(panics, ["Handled failure"]);
+
+        // Other tasks keep running
+        tx.send(1).unwrap();
+        executor.update(&mut panics);
+        assert_eq!(other.now_or_never(), Some(Ok(1)));
+        assert!(executor.tasks.is_empty());
+    }
+
+    #[test]
+    fn phases() {
+        let mut executor = Executor::<Vec<Phase>>::new();
+        let spawner = executor.spawner();
+
+        for phase in [Phase::Idle, Phase::PostLayout, Phase::PreLayout] {
+            spawner.spawn_in_phase(
+                phase,
+                FnOnceEffect::new(move |v: &mut Vec<_>| v.push(phase)),
+            );
+        }
+
+        let mut order = Vec::new();
+        executor.update_phase(Phase::PreLayout, &mut order);
+        assert_eq!(order, [Phase::PreLayout]);
+
+        // Tasks of later phases stay queued
+        executor.update_phase(Phase::PreLayout, &mut order);
+        assert_eq!(order, [Phase::PreLayout]);
+
+        executor.update(&mut order);
+        assert_eq!(order, [Phase::PreLayout, Phase::PostLayout, Phase::Idle]);
+        assert!(executor.tasks.is_empty());
+    }
+
+    #[test]
+    fn skip_phase() {
+        let mut executor = Executor::<Vec<Phase>>::new();
+        let woken = Arc::new(AtomicBool::new(false));
+        executor.set_waker(waker_fn::waker_fn({
+            let woken = woken.clone();
+            move || woken.store(true, Ordering::SeqCst)
+        }));
+
+        let spawner = executor.spawner();
+        for phase in [Phase::PreLayout, Phase::Idle, Phase::Idle] {
+            spawner.spawn_in_phase(
+                phase,
+                FnOnceEffect::new(move |v: &mut Vec<_>| v.push(phase)),
+            );
+        }
+
+        let mut order = Vec::new();
+        executor.update_phase(Phase::PreLayout, &mut order);
+        woken.store(false, Ordering::SeqCst);
+
+        assert_eq!(
+            executor.skip_phase(Phase::Idle),
+            UpdateStats {
+                polled: 0,
+                deferred: 2
+            }
+        );
+        assert_eq!(order, [Phase::PreLayout]);
+
+        // The skipped tasks request another update
+        assert!(woken.load(Ordering::SeqCst));
+        executor.update_phase(Phase::Idle, &mut order);
+        assert_eq!(order, [Phase::PreLayout, Phase::Idle, Phase::Idle]);
+
+        woken.store(false, Ordering::SeqCst);
+        assert_eq!(executor.skip_phase(Phase::Idle), UpdateStats::default());
+        assert!(!woken.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn timers() {
+        let clock = MockClock::new();
+        let mut executor = Executor::<()>::new();
+        executor.set_timers(clock.handle());
+
+        let mut handle = executor.spawner().spawn_future(async {
+            time::sleep(Duration::from_secs(60)).await;
+            5
+        });
+
+        executor.update(&mut ());
+        assert_eq!((&mut handle).now_or_never(), None);
+
+        clock.advance(Duration::from_secs(60));
+        executor.update(&mut ());
+        assert_eq!(handle.now_or_never(), Some(Ok(5)));
+    }
+
+    #[test]
+    fn idle() {
+        let mut executor = Executor::<()>::new();
+        let spawner = executor.spawner();
+        assert!(executor.is_idle());
+
+        let (tx, rx) = oneshot::channel();
+        let _waiting = spawner.spawn_future(rx);
+        assert!(!executor.is_idle());
+
+        executor.update(&mut ());
+        assert!(executor.is_idle());
+
+        tx.send(()).unwrap();
+        assert!(!executor.is_idle());
+        executor.update(&mut ());
+        assert!(executor.is_idle());
+
+        // A task which yields to continue its work is not idle
+        let mut remaining = 3;
+        let _yielding = spawner.spawn_future(futures::future::poll_fn(move |cx| {
+            if remaining == 0 {
+                return Poll::Ready(());
+            }
+
+            remaining -= 1;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }));
+
+        let mut updates = 0;
+        while !executor.is_idle() {
+            executor.update(&mut ());
+            updates += 1;
+        }
+
+        assert_eq!(updates, 4);
+        assert!(executor.tasks.is_empty());
+    }
+
+    #[test]
+    fn budget() {
+        /// Takes 10ms of virtual time to poll
+        struct Slow(MockClock);
+
+        impl Effect<usize> for Slow {
+            fn poll_effect(self: Pin<&mut Self>, count: &mut usize, _: &mut Context) -> Poll<()> {
+                self.0.advance(Duration::from_millis(10));
+                *count += 1;
+                Poll::Ready(())
+            }
+        }
+
+        let clock = MockClock::new();
+        let mut executor = Executor::<usize>::new();
+        executor.set_timers(clock.handle());
+
+        let woken = Arc::new(AtomicBool::new(false));
+        executor.set_waker(waker_fn::waker_fn({
+            let woken = woken.clone();
+            move || woken.store(true, Ordering::SeqCst)
+        }));
+
+        let spawner = executor.spawner();
+        for _ in 0..8 {
+            spawner.spawn(Slow(clock.clone()));
+        }
+
+        woken.store(false, Ordering::SeqCst);
+
+        let mut count = 0;
+        let stats = executor.update_with_budget(Duration::from_millis(25), &mut count);
+        assert_eq!(count, 3);
+        assert_eq!(
+            stats,
+            UpdateStats {
+                polled: 3,
+                deferred: 5
+            }
+        );
+
+        // The deferred tasks request another update
+        assert!(woken.load(Ordering::SeqCst));
+        assert!(executor
+            .poll_update(Context::from_waker(&noop_waker()), &mut count)
+            .is_ready());
+        assert_eq!(count, 8);
+
+        // At least one task is polled each update
+        spawner.spawn(Slow(clock.clone()));
+        let stats = executor.update_with_budget(Duration::ZERO, &mut count);
+        assert_eq!(
+            stats,
+            UpdateStats {
+                polled: 1,
+                deferred: 0
+            }
+        );
+    }
+}
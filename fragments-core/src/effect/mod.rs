@@ -1,10 +1,15 @@
+mod component;
 mod executor;
 mod future;
+mod query;
 mod signal;
 mod stream;
 
+pub(crate) use component::*;
 pub use executor::*;
 pub use future::*;
+pub use query::{QueryDiff, QuerySignal};
+pub(crate) use query::{QueryObserver, QueryState};
 use futures::Stream;
 pub(crate) use signal::*;
 pub use stream::*;
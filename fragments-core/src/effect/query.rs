@@ -0,0 +1,102 @@
+use std::{
+    collections::BTreeSet,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use flax::{Entity, World};
+use futures::Stream;
+use pin_project::pin_project;
+
+use crate::{frame::WorldObserver, signal::Signal};
+
+/// Describes a change to the set of entities matching a query
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum QueryDiff {
+    /// The entity started matching the query
+    Added(Entity),
+    /// The entity no longer matches the query
+    Removed(Entity),
+    /// A component fetched by the query was modified
+    Modified(Entity),
+}
+
+/// Yields the changes to the entities matching a query, batched each time the frame
+/// [observes](crate::frame::Frame::observe_changes) the world.
+///
+/// Entities which already match the query when the signal is created are reported as added.
+///
+/// See [`Frame::query_signal`]
+#[pin_project]
+pub struct QuerySignal {
+    #[pin]
+    diffs: flume::r#async::RecvStream<'static, Vec<QueryDiff>>,
+}
+
+impl QuerySignal {
+    pub(crate) fn new(diffs: flume::Receiver<Vec<QueryDiff>>) -> Self {
+        Self {
+            diffs: diffs.into_stream(),
+        }
+    }
+}
+
+impl<'a> Signal<'a> for QuerySignal {
+    type Item = Vec<QueryDiff>;
+
+    fn poll_changed(self: Pin<&'a mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().diffs.poll_next(cx)
+    }
+}
+
+/// Returns the entities currently matching the query, and those modified since the last call
+pub(crate) type QueryState = Box<dyn FnMut(&World) -> (BTreeSet<Entity>, Vec<Entity>)>;
+
+/// Diffs the entities matching a query each time the world changes.
+///
+/// Stops when the receiving [`QuerySignal`] is dropped.
+pub(crate) struct QueryObserver {
+    state: QueryState,
+    matched: BTreeSet<Entity>,
+    tx: flume::Sender<Vec<QueryDiff>>,
+}
+
+impl QueryObserver {
+    pub fn new(state: QueryState, tx: flume::Sender<Vec<QueryDiff>>) -> Self {
+        Self {
+            state,
+            matched: BTreeSet::new(),
+            tx,
+        }
+    }
+}
+
+impl WorldObserver for QueryObserver {
+    fn observe(&mut self, world: &World) -> bool {
+        if self.tx.is_disconnected() {
+            return false;
+        }
+
+        let (matched, modified) = (self.state)(world);
+
+        let added = matched
+            .difference(&self.matched)
+            .map(|&id| QueryDiff::Added(id));
+        let removed = self
+            .matched
+            .difference(&matched)
+            .map(|&id| QueryDiff::Removed(id));
+
+        // Flax reports newly added entities as modified as well. Those are removed here, as they
+        // are already reported as added.
+        let modified = modified
+            .into_iter()
+            .filter(|id| self.matched.contains(id) && matched.contains(id))
+            .map(QueryDiff::Modified);
+
+        let diffs: Vec<_> = added.chain(removed).chain(modified).collect();
+        self.matched = matched;
+
+        diffs.is_empty() || self.tx.send(diffs).is_ok()
+    }
+}
@@ -3,8 +3,8 @@ use std::{
     collections::BTreeMap,
 };
 
-use dashmap::DashMap;
-use flax::Component;
+use dashmap::{mapref::entry::Entry, DashMap};
+use flax::{Component, Entity};
 
 use crate::frame::Frame;
 
@@ -30,6 +30,24 @@ impl<T: Clone> EventHandler<T> for flume::Sender<T> {
     }
 }
 
+/// Emitted by the backend once at the start of each frame.
+///
+/// Used to drive per frame updates such as [animations](crate::animation).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RedrawEvent;
+
+/// Emitted when an effect of a widget panics.
+///
+/// The panicking effect is removed and the cleanup of the widget is run, which aborts all effects
+/// of the widget. The widget itself stays mounted with its current components, while the rest of
+/// the application keeps running.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskPanicked {
+    /// The widget which spawned the effect
+    pub entity: Entity,
+    pub message: String,
+}
+
 /// Stores all the handlers for an event of a specific type
 pub struct EventDispatcher<T> {
     handlers: Vec<Box<dyn EventHandler<T>>>,
@@ -89,12 +107,33 @@ impl EventRegistry {
     }
 
     /// Emits a global event to all listeners
+    ///
+    /// Handlers may register new handlers for the same event, which receive the next emitted
+    /// event.
     pub fn emit<T: 'static>(&self, frame: &mut Frame, event: &T) {
-        if let Some(mut dispatcher) = self.dispatchers.get_mut(&TypeId::of::<T>()) {
-            dispatcher
-                .downcast_mut::<EventDispatcher<T>>()
-                .unwrap()
-                .emit(frame, event);
+        let key = TypeId::of::<T>();
+
+        // Take the dispatcher out of the map to not hold the lock while handlers run
+        let Some((_, dispatcher)) = self.dispatchers.remove(&key) else {
+            return;
+        };
+
+        let mut dispatcher = dispatcher.downcast::<EventDispatcher<T>>().unwrap();
+        dispatcher.emit(frame, event);
+
+        match self.dispatchers.entry(key) {
+            Entry::Occupied(mut entry) => {
+                // Keep the handlers which were registered while emitting
+                let registered = entry
+                    .get_mut()
+                    .downcast_mut::<EventDispatcher<T>>()
+                    .unwrap();
+                dispatcher.handlers.append(&mut registered.handlers);
+                entry.insert(dispatcher);
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(dispatcher);
+            }
         }
     }
 }
@@ -1,8 +1,20 @@
-use std::sync::Arc;
+use std::{collections::BTreeSet, sync::Arc};
 
-use flax::World;
+use flax::{entity_ids, Entity, Fetch, FetchExt, Query, World};
 
-use crate::{effect::TaskSpawner, events::EventRegistry, Scope, Widget};
+use crate::{
+    effect::{QueryObserver, QuerySignal, TaskSpawner},
+    events::EventRegistry,
+    Scope, Widget,
+};
+
+/// Observes changes to the world, such as for [`Scope::component_signal`]
+pub(crate) trait WorldObserver {
+    /// Invoked after the world has changed
+    ///
+    /// Returns `true` if the observer should be kept, `false` if it should be removed
+    fn observe(&mut self, world: &World) -> bool;
+}
 
 /// Contains the UI state
 ///
@@ -12,19 +24,135 @@ pub struct Frame {
     /// Handle allowing spawning of tasks
     pub spawner: TaskSpawner<Frame>,
     pub events: Arc<EventRegistry>,
+    observers: Vec<Box<dyn WorldObserver>>,
+    /// The change tick of the world when the observers last ran
+    observed_tick: u32,
 }
 
 impl Frame {
     pub fn new(world: World, spawner: TaskSpawner<Frame>, events: Arc<EventRegistry>) -> Self {
         Self {
+            observed_tick: world.change_tick(),
             world,
             spawner,
             events,
+            observers: Vec::new(),
         }
     }
 
-    pub fn spawn_root(&mut self, widget: impl Widget) {
+    /// Notifies the signals observing the world, such as [`Scope::component_signal`] and
+    /// [`Frame::query_signal`], of the changes since the last call.
+    ///
+    /// Does nothing if the world has not changed. Called by the backend once per frame, and after
+    /// the systems which modify the world.
+    pub fn observe_changes(&mut self) {
+        let tick = self.world.change_tick();
+        if tick == self.observed_tick {
+            return;
+        }
+
+        self.observed_tick = tick;
+
+        let world = &self.world;
+        self.observers
+            .retain_mut(|observer| observer.observe(world));
+    }
+
+    /// Adds an observer which runs each time the world has changed
+    pub(crate) fn observe(&mut self, observer: impl 'static + WorldObserver) {
+        self.observers.push(Box::new(observer));
+    }
+
+    /// Mounts a widget at the root of the tree, returning its entity
+    pub fn spawn_root(&mut self, widget: impl Widget) -> Entity {
         let mut scope = Scope::spawn(self);
         widget.mount(&mut scope);
+        scope.id()
+    }
+
+    /// Returns a signal which yields the entities added, removed and modified for a query.
+    ///
+    /// An entity is modified when any component fetched by `fetch` is modified, as reported by
+    /// flax change tracking. The changes are collected when the world has changed since the last
+    /// [`observe_changes`](Self::observe_changes), and collection stops when the signal is
+    /// dropped.
+    pub fn query_signal<Q>(&mut self, fetch: Q) -> QuerySignal
+    where
+        Q: 'static + for<'x> Fetch<'x> + FetchExt + Clone,
+    {
+        let mut matched = Query::new((entity_ids(), fetch.clone()));
+        let mut modified = Query::new((entity_ids(), fetch.modified()));
+
+        let state = Box::new(move |world: &World| {
+            let matched: BTreeSet<_> = matched.borrow(world).iter().map(|(id, _)| id).collect();
+            let modified = modified.borrow(world).iter().map(|(id, _)| id).collect();
+            (matched, modified)
+        });
+
+        let (tx, rx) = flume::unbounded();
+
+        // Report the entities which already match right away
+        let mut observer = QueryObserver::new(state, tx);
+        observer.observe(&self.world);
+        self.observe(observer);
+
+        QuerySignal::new(rx)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use futures::FutureExt;
+
+    use crate::{components::text, effect::QueryDiff, signal::Signal, testing::TestApp};
+
+    use super::*;
+
+    #[test]
+    fn query_signal() {
+        let mut app = TestApp::new();
+
+        let a = Entity::builder()
+            .set(text(), "a".into())
+            .spawn(app.world_mut());
+
+        let mut signal = app.frame_mut().query_signal(text());
+        assert_eq!(
+            signal.next_value().now_or_never(),
+            Some(Some(vec![QueryDiff::Added(a)]))
+        );
+
+        // Nothing changed
+        app.run_until_stalled();
+        assert_eq!(signal.next_value().now_or_never(), None);
+
+        let b = Entity::builder()
+            .set(text(), "b".into())
+            .spawn(app.world_mut());
+        app.run_until_stalled();
+        assert_eq!(
+            signal.next_value().now_or_never(),
+            Some(Some(vec![QueryDiff::Added(b)]))
+        );
+
+        *app.world_mut().get_mut(a, text()).unwrap() = "c".into();
+        app.run_until_stalled();
+        assert_eq!(
+            signal.next_value().now_or_never(),
+            Some(Some(vec![QueryDiff::Modified(a)]))
+        );
+
+        app.world_mut().remove(b, text()).unwrap();
+        app.world_mut().despawn(a).unwrap();
+        app.run_until_stalled();
+        assert_eq!(
+            signal.next_value().now_or_never(),
+            Some(Some(vec![QueryDiff::Removed(a), QueryDiff::Removed(b)]))
+        );
+
+        // An unrelated change is not reported
+        app.world_mut().spawn();
+        app.run_until_stalled();
+        assert_eq!(signal.next_value().now_or_never(), None);
     }
 }
@@ -1,3 +1,4 @@
+pub mod animation;
 pub mod assets;
 pub mod components;
 pub mod context;
@@ -9,6 +10,7 @@ pub mod frame;
 pub mod layout;
 mod scope;
 pub mod signal;
+pub mod testing;
 pub mod time;
 mod widget;
 
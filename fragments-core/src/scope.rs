@@ -1,23 +1,29 @@
 use std::{
     marker::PhantomData,
     task::{Context, Poll},
+    time::Duration,
 };
 
 use atomic_refcell::AtomicRef;
 use flax::{
     archetype::RefMut, child_of, name, Component, ComponentValue, Entity, EntityBuilder, EntityRef,
-    EntityRefMut, World,
+    EntityRefMut, Query, World,
 };
 use futures::{Future, SinkExt, Stream};
 use pin_project::pin_project;
 
 use crate::{
-    components::{on_cleanup, ordered_children, tasks},
+    animation::{AnimationHandle, Easing, Lerp},
+    components::{animations, on_cleanup, ordered_children, tasks, timers},
     context::ContextKey,
-    effect::{Effect, FutureEffect, SignalEffect, StreamEffect, TaskSpawner},
-    events::EventHandler,
+    effect::{
+        ComponentObserver, Effect, FutureEffect, Phase, SignalEffect, StreamEffect, TaskHandle,
+        TaskSpawner,
+    },
+    events::{EventHandler, TaskPanicked},
     frame::Frame,
-    signal::Signal,
+    signal::{Mutable, MutableSignal, Signal},
+    time::{self, TimersHandle},
     Widget,
 };
 
@@ -65,16 +71,57 @@ impl<'a> Scope<'a> {
     where
         E: 'static + for<'x> Effect<Scope<'x>>,
     {
+        self.create_effect_in_phase(Phase::default(), effect)
+    }
+
+    /// Creates a new effect which runs in the given phase of each frame.
+    ///
+    /// For example, an effect in [`Phase::PostLayout`] observes the layout of the current frame.
+    pub fn create_effect_in_phase<E>(&mut self, phase: Phase, effect: E)
+    where
+        E: 'static + for<'x> Effect<Scope<'x>>,
+    {
+        let handle = self.spawn_effect(phase, effect);
+
+        self.on_cleanup(move || handle.abort());
+
+        // pub fn on_cleanup(&mut self, func: impl 'static + FnOnce(&mut Scope)) {
+        self.flush();
+    }
+
+    /// Spawns an effect in the scope of the widget without stopping it on unmount
+    fn spawn_effect<E>(&mut self, phase: Phase, effect: E) -> TaskHandle
+    where
+        E: 'static + for<'x> Effect<Scope<'x>>,
+    {
+        self.flush();
+
         let effect = Lift {
             effect,
             id: self.id,
+            timers: self.consume_context(timers()).map(|v| v.clone()),
         };
 
-        let handle = self.frame.spawner.spawn(effect);
-        self.on_cleanup(move || handle.abort());
+        let id = self.id;
+        let on_panic = move |frame: &mut Frame, message: &str| {
+            // Run the cleanup of the widget, as it may be left in an inconsistent state
+            if let Ok(cleanup) = frame.world.remove(id, on_cleanup()) {
+                drop(cleanup);
+            }
 
-        // pub fn on_cleanup(&mut self, func: impl 'static + FnOnce(&mut Scope)) {
-        self.flush();
+            let events = frame.events.clone();
+            events.emit(
+                frame,
+                &TaskPanicked {
+                    entity: id,
+                    message: message.into(),
+                },
+            );
+        };
+
+        self.frame
+            .spawner
+            .spawn_task(phase, effect, Some(Box::new(on_panic)))
     }
 
     /// Executes `func` within the scope of the widget when the signal is emitted
@@ -95,6 +142,89 @@ impl<'a> Scope<'a> {
         self.create_effect(FutureEffect::new(future, func))
     }
 
+    /// Like [`Self::use_async`], but the future is not aborted when the widget is unmounted.
+    ///
+    /// The caller is responsible for aborting the returned handle.
+    pub(crate) fn spawn_async<T>(
+        &mut self,
+        future: impl 'static + Future<Output = T>,
+        func: impl 'static + FnOnce(&mut Scope, T),
+    ) -> TaskHandle {
+        self.spawn_effect(Phase::default(), FutureEffect::new(future, func))
+    }
+
+    /// Returns a signal which yields the value of `component` on the entity each time it changes.
+    ///
+    /// Changes, including those made through [`Scope::set`] and by systems, are detected using
+    /// flax change tracking when the frame [observes](Frame::observe_changes) the world. The
+    /// signal yields `None` if the component is removed, and is closed when the entity is
+    /// despawned or the current widget is unmounted.
+    pub fn component_signal<T: ComponentValue + Clone>(
+        &mut self,
+        id: Entity,
+        component: Component<T>,
+    ) -> MutableSignal<Option<T>> {
+        self.flush();
+
+        let mut query = Query::new(component.modified());
+        let initial = query.borrow(&self.frame.world).get(id).ok().cloned();
+
+        let value = Mutable::new(initial);
+        let signal = value.signal();
+
+        self.frame
+            .observe(ComponentObserver::new(self.id, id, component, query, value));
+
+        signal
+    }
+
+    /// Uses `handle` for the timers, such as [`time::sleep`], created by the effects of this
+    /// widget and its descendants.
+    ///
+    /// Only affects effects created after this call.
+    pub fn use_timers(&mut self, handle: TimersHandle) {
+        self.provide_context(timers(), handle);
+    }
+
+    /// Animates `component` of the widget from its current value to `target`, stepping once each
+    /// [`RedrawEvent`](crate::events::RedrawEvent).
+    ///
+    /// If the component is not set, it is set to `target` directly. Use the returned handle to
+    /// chain further tweens or cancel the animation. The animation stops when the widget is
+    /// unmounted.
+    ///
+    /// Animating a component which is already animated cancels the previous animation, and the
+    /// new one starts from the current value.
+    pub fn animate<T: ComponentValue + Lerp>(
+        &mut self,
+        component: Component<T>,
+        target: T,
+        duration: Duration,
+        easing: Easing,
+    ) -> AnimationHandle<T> {
+        self.flush();
+
+        let timers = self
+            .consume_context(timers())
+            .map(|v| v.clone())
+            .unwrap_or_else(time::current);
+
+        let handle = AnimationHandle::new(self.frame, self.id, component, timers);
+
+        let prev = self
+            .entity_mut()
+            .entry(animations())
+            .or_default()
+            .insert(component.key(), handle.cancel_handle());
+
+        if let Some(prev) = prev {
+            prev.cancel();
+        }
+
+        handle.then(target, duration, easing);
+        handle
+    }
+
     pub fn on_cleanup(&mut self, func: impl 'static + Send + Sync + FnOnce()) {
         self.flush();
         self.frame
@@ -226,7 +356,6 @@ impl<'a> Scope<'a> {
     /// Consumes a context provided higher up in the tree.
     pub fn consume_context<T: ComponentValue>(&self, key: ContextKey<T>) -> Option<AtomicRef<T>> {
         let world = &self.frame.world;
-        tracing::info!(?self.id, "World:  {world:#?}");
         let mut cur = world.entity(self.id).unwrap();
         let key = key.into_raw();
 
@@ -320,6 +449,11 @@ impl<'a> Scope<'a> {
             .expect("Entity was despawned")
     }
 
+    /// Returns the entity id of the widget
+    pub fn id(&self) -> Entity {
+        self.id
+    }
+
     pub fn frame_mut(&mut self) -> &mut &'a mut Frame {
         &mut self.frame
     }
@@ -340,6 +474,7 @@ struct Lift<E> {
     #[pin]
     effect: E,
     id: Entity,
+    timers: Option<TimersHandle>,
 }
 
 impl<E> Effect<Frame> for Lift<E>
@@ -352,6 +487,7 @@ where
         cx: &mut Context<'_>,
     ) -> Poll<()> {
         let p = self.project();
+        let _guard = p.timers.clone().map(time::enter);
 
         match Scope::try_from_id(frame, *p.id) {
             Some(mut v) => p.effect.poll_effect(&mut v, cx),
@@ -383,3 +519,173 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    use futures::FutureExt;
+    use palette::Srgba;
+    use parking_lot::Mutex;
+
+    use crate::{
+        components::{color, text},
+        testing::TestApp,
+    };
+
+    use super::*;
+
+    struct Empty;
+
+    impl Widget for Empty {
+        fn mount(self, _: &mut Scope<'_>) {}
+    }
+
+    #[test]
+    fn component_signal() {
+        let mut app = TestApp::new();
+        let root = app.mount(Empty);
+        let target = app.world_mut().spawn();
+
+        let mut signal = Scope::try_from_id(app.frame_mut(), root)
+            .unwrap()
+            .component_signal(target, text());
+
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(None)));
+
+        // Nothing changed
+        app.run_until_stalled();
+        assert_eq!(signal.next_value().now_or_never(), None);
+
+        app.world_mut().set(target, text(), "a".into()).unwrap();
+        app.run_until_stalled();
+        assert_eq!(
+            signal.next_value().now_or_never(),
+            Some(Some(Some("a".into())))
+        );
+
+        *app.world_mut().get_mut(target, text()).unwrap() = "b".into();
+        app.run_until_stalled();
+        assert_eq!(
+            signal.next_value().now_or_never(),
+            Some(Some(Some("b".into())))
+        );
+
+        app.world_mut().remove(target, text()).unwrap();
+        app.run_until_stalled();
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(None)));
+
+        // Closed when the entity is despawned
+        app.world_mut().despawn(target).unwrap();
+        app.run_until_stalled();
+        assert_eq!(signal.next_value().now_or_never(), Some(None));
+    }
+
+    #[test]
+    fn component_signal_unmount() {
+        let mut app = TestApp::new();
+        let root = app.mount(Empty);
+        let target = app.world_mut().spawn();
+
+        let mut signal = Scope::try_from_id(app.frame_mut(), root)
+            .unwrap()
+            .component_signal(target, text());
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(None)));
+
+        // Closed when the observing widget is unmounted
+        app.world_mut().despawn(root).unwrap();
+        app.run_until_stalled();
+        assert_eq!(signal.next_value().now_or_never(), Some(None));
+        assert!(app.world().is_alive(target));
+    }
+
+    /// Sets the text to each value of a signal, and panics on `"panic"`
+    struct Echo(MutableSignal<&'static str>);
+
+    impl Widget for Echo {
+        fn mount(self, scope: &mut Scope<'_>) {
+            scope.use_signal(self.0, |scope, value| {
+                if value == "panic" {
+                    panic!("Echo panicked");
+                }
+
+                scope.set(text(), value.into());
+            });
+        }
+    }
+
+    /// An [`Echo`] with another effect and a cleanup
+    struct Panicking {
+        value: MutableSignal<&'static str>,
+        alpha: MutableSignal<f32>,
+        cleaned_up: Arc<AtomicBool>,
+    }
+
+    impl Widget for Panicking {
+        fn mount(self, scope: &mut Scope<'_>) {
+            let cleaned_up = self.cleaned_up;
+            scope.on_cleanup(move || cleaned_up.store(true, Ordering::SeqCst));
+
+            scope.use_signal(self.alpha, |scope, alpha| {
+                scope.set(color(), Srgba::new(1.0, 1.0, 1.0, alpha))
+            });
+
+            Echo(self.value).mount(scope);
+        }
+    }
+
+    struct PanicListener(Arc<Mutex<Vec<TaskPanicked>>>);
+
+    impl Widget for PanicListener {
+        fn mount(self, scope: &mut Scope<'_>) {
+            scope.on_global_event(move |_, event: &TaskPanicked| self.0.lock().push(event.clone()));
+        }
+    }
+
+    #[test]
+    fn effect_panic() {
+        let mut app = TestApp::new();
+
+        let panics = Arc::new(Mutex::new(Vec::new()));
+        app.mount(PanicListener(panics.clone()));
+
+        let value = Mutable::new("a");
+        let alpha = Mutable::new(1.0);
+        let cleaned_up = Arc::new(AtomicBool::new(false));
+        let id = app.mount(Panicking {
+            value: value.signal(),
+            alpha: alpha.signal(),
+            cleaned_up: cleaned_up.clone(),
+        });
+
+        let sibling_value = Mutable::new("a");
+        let sibling = app.mount(Echo(sibling_value.signal()));
+
+        value.set("panic");
+        app.run_until_stalled();
+
+        assert_eq!(
+            *panics.lock(),
+            [TaskPanicked {
+                entity: id,
+                message: "Echo panicked".into()
+            }]
+        );
+        assert!(cleaned_up.load(Ordering::SeqCst));
+
+        // All effects of the widget are aborted, but it stays mounted
+        assert!(app.world().is_alive(id));
+        alpha.set(0.5);
+        app.run_until_stalled();
+        assert_eq!(app.get(id, color()), Some(Srgba::new(1.0, 1.0, 1.0, 1.0)));
+        assert_eq!(app.get(id, text()).as_deref(), Some("a"));
+
+        // Other widgets keep running
+        sibling_value.set("b");
+        app.run_until_stalled();
+        assert_eq!(app.get(sibling, text()).as_deref(), Some("b"));
+    }
+}
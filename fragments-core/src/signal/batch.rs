@@ -0,0 +1,146 @@
+use std::{cell::RefCell, sync::Arc};
+
+use super::waiter::Hook;
+
+thread_local! {
+    /// Hooks woken during the current batch, if any
+    static BATCH: RefCell<Option<Vec<Arc<Hook>>>> = RefCell::new(None);
+}
+
+/// Executes `func` as a single transaction.
+///
+/// Observers are not notified of any changes made on the current thread until the outermost batch
+/// ends, at which point each observer is woken once. This prevents observers, regardless of which
+/// thread they run on, from seeing partially updated state across multiple values.
+pub fn batch<R>(func: impl FnOnce() -> R) -> R {
+    struct Flush {
+        is_outermost: bool,
+    }
+
+    impl Drop for Flush {
+        fn drop(&mut self) {
+            if !self.is_outermost {
+                return;
+            }
+
+            let Some(mut hooks) = BATCH.with(|batch| batch.borrow_mut().take()) else {
+                return;
+            };
+
+            hooks.sort_by_key(|v| Arc::as_ptr(v));
+            hooks.dedup_by(|a, b| Arc::ptr_eq(a, b));
+
+            // Mark every hook before waking any observer so that no observer sees only a part of
+            // the batch
+            for hook in &hooks {
+                hook.mark_changed();
+            }
+
+            for hook in hooks {
+                hook.wake_now();
+            }
+        }
+    }
+
+    let is_outermost = BATCH.with(|batch| {
+        let mut batch = batch.borrow_mut();
+        if batch.is_none() {
+            *batch = Some(Vec::new());
+            true
+        } else {
+            false
+        }
+    });
+
+    // Make sure the wakes are not lost even if `func` panics
+    let _flush = Flush { is_outermost };
+
+    func()
+}
+
+/// Defers waking the hook until the end of the current batch.
+///
+/// Returns false if there is no batch on the current thread.
+pub(crate) fn defer_wake(hook: &Arc<Hook>) -> bool {
+    BATCH.with(|batch| match &mut *batch.borrow_mut() {
+        Some(hooks) => {
+            hooks.push(hook.clone());
+            true
+        }
+        None => false,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        task::Context,
+        thread,
+    };
+
+    use futures::{task::ArcWake, FutureExt};
+
+    use crate::signal::{Mutable, Signal};
+
+    use super::*;
+
+    #[derive(Default)]
+    struct CountWaker(AtomicUsize);
+
+    impl ArcWake for CountWaker {
+        fn wake_by_ref(arc_self: &Arc<Self>) {
+            arc_self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn batch() {
+        let value = Mutable::new(1);
+
+        let mut signal = value.signal();
+        let count = Arc::new(CountWaker::default());
+        let waker = futures::task::waker(count.clone());
+
+        let mut poll =
+            || std::pin::Pin::new(&mut signal).poll_changed(&mut Context::from_waker(&waker));
+
+        assert!(poll().is_ready());
+        assert!(poll().is_pending());
+
+        super::batch(|| {
+            *value.write() = 2;
+
+            super::batch(|| {
+                *value.write() = 3;
+            });
+
+            // Not observable until the outermost batch ends
+            assert!(poll().is_pending());
+            *value.write() = 4;
+        });
+
+        assert_eq!(count.0.load(Ordering::Relaxed), 1);
+        assert_eq!(poll(), std::task::Poll::Ready(Some(4)));
+    }
+
+    #[test]
+    fn batch_across_threads() {
+        let a = Mutable::new(1);
+        let b = Mutable::new(1);
+
+        let mut signal = a.signal().zip(b.signal());
+        assert_eq!(signal.next_value().now_or_never(), Some(Some((1, 1))));
+
+        let observer = thread::spawn(move || futures::executor::block_on(signal.next_value()));
+
+        super::batch(|| {
+            *a.write() = 2;
+            thread::yield_now();
+            *b.write() = 2;
+        });
+
+        // The intermediate state of `(2, 1)` is never observed
+        assert_eq!(observer.join().unwrap(), Some((2, 2)));
+    }
+}
@@ -0,0 +1,253 @@
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+
+use parking_lot::Mutex;
+
+use super::{
+    waiter::{Hook, HookList},
+    Signal,
+};
+
+struct Buffer<T> {
+    values: VecDeque<T>,
+    /// Sequence number of the first value in the buffer
+    head: u64,
+}
+
+struct Inner<T> {
+    buffer: Mutex<Buffer<T>>,
+    capacity: usize,
+    senders: AtomicUsize,
+    hooks: HookList,
+}
+
+impl<T> Inner<T> {
+    fn tail(&self) -> u64 {
+        let buffer = self.buffer.lock();
+        buffer.head + buffer.values.len() as u64
+    }
+
+    fn subscribe(self: &Arc<Self>, next: u64) -> BroadcastReceiver<T> {
+        let hook = Arc::new(Hook::new(false));
+        self.hooks.push(Arc::downgrade(&hook));
+
+        BroadcastReceiver {
+            hook,
+            next,
+            inner: self.clone(),
+        }
+    }
+}
+
+/// Sends values to every [`BroadcastReceiver`] of a [`broadcast_channel`]
+pub struct BroadcastSender<T> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T> BroadcastSender<T> {
+    /// Send a value to all receivers.
+    ///
+    /// If the buffer is full the oldest value is discarded, and receivers which have not yet seen
+    /// it skip ahead.
+    pub fn send(&self, value: T) {
+        {
+            let mut buffer = self.inner.buffer.lock();
+            if buffer.values.len() == self.inner.capacity {
+                buffer.values.pop_front();
+                buffer.head += 1;
+            }
+
+            buffer.values.push_back(value);
+        }
+
+        self.inner.hooks.wake_all()
+    }
+
+    /// Creates a new receiver which only observes values sent after this call
+    pub fn subscribe(&self) -> BroadcastReceiver<T> {
+        self.inner.subscribe(self.inner.tail())
+    }
+}
+
+impl<T> Drop for BroadcastSender<T> {
+    fn drop(&mut self) {
+        if self.inner.senders.fetch_sub(1, Ordering::Relaxed) == 1 {
+            self.inner.hooks.wake_all();
+        }
+    }
+}
+
+impl<T> Clone for BroadcastSender<T> {
+    fn clone(&self) -> Self {
+        self.inner.senders.fetch_add(1, Ordering::Relaxed);
+
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for BroadcastSender<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("BroadcastSender").finish()
+    }
+}
+
+/// Receives every value sent through a [`broadcast_channel`].
+///
+/// A receiver which falls more than `capacity` values behind skips to the oldest value still
+/// retained, so it always observes at least the latest value.
+pub struct BroadcastReceiver<T> {
+    hook: Arc<Hook>,
+    /// Sequence number of the next value to yield
+    next: u64,
+    inner: Arc<Inner<T>>,
+}
+
+impl<T> BroadcastReceiver<T> {
+    /// Returns the number of retained values which have not yet been received
+    pub fn pending(&self) -> usize {
+        let buffer = self.inner.buffer.lock();
+        let tail = buffer.head + buffer.values.len() as u64;
+        (tail - self.next.max(buffer.head)) as usize
+    }
+}
+
+impl<T> Clone for BroadcastReceiver<T> {
+    /// The new receiver observes the same values as `self` from this point on
+    fn clone(&self) -> Self {
+        self.inner.subscribe(self.next)
+    }
+}
+
+impl<T> std::fmt::Debug for BroadcastReceiver<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BroadcastReceiver")
+            .field("next", &self.next)
+            .finish()
+    }
+}
+
+impl<'a, T> Signal<'a> for BroadcastReceiver<T>
+where
+    T: 'a + Clone,
+{
+    type Item = T;
+
+    fn poll_changed(mut self: Pin<&'a mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // Store the waker before inspecting the buffer so that no send is missed in between
+        self.hook.set_waker(cx.waker().clone());
+        self.hook.take_changed();
+
+        let this = &mut *self;
+        let buffer = this.inner.buffer.lock();
+
+        if this.next < buffer.head {
+            tracing::debug!(
+                skipped = buffer.head - this.next,
+                "Broadcast receiver lagged behind"
+            );
+            this.next = buffer.head;
+        }
+
+        if let Some(value) = buffer.values.get((this.next - buffer.head) as usize) {
+            this.next += 1;
+            Poll::Ready(Some(value.clone()))
+        } else if this.inner.senders.load(Ordering::Relaxed) == 0 {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// Creates a channel where every receiver observes every value sent.
+///
+/// Up to `capacity` values are retained for receivers which have not yet observed them.
+///
+/// # Panics
+///
+/// If `capacity` is zero
+pub fn broadcast_channel<T>(capacity: usize) -> (BroadcastSender<T>, BroadcastReceiver<T>) {
+    assert!(capacity > 0, "Broadcast capacity must be non-zero");
+
+    let inner = Arc::new(Inner {
+        buffer: Mutex::new(Buffer {
+            values: VecDeque::with_capacity(capacity),
+            head: 0,
+        }),
+        capacity,
+        senders: AtomicUsize::new(1),
+        hooks: HookList::default(),
+    });
+
+    let receiver = inner.subscribe(0);
+
+    (BroadcastSender { inner }, receiver)
+}
+
+#[cfg(test)]
+mod test {
+    use futures::FutureExt;
+
+    use super::*;
+
+    #[test]
+    fn broadcast() {
+        let (tx, mut rx) = broadcast_channel(2);
+        let mut rx2 = rx.clone();
+
+        tx.send(1);
+        tx.send(2);
+
+        assert_eq!(rx.next_value().now_or_never(), Some(Some(1)));
+        assert_eq!(rx.next_value().now_or_never(), Some(Some(2)));
+        assert_eq!(rx.next_value().now_or_never(), None);
+
+        // Every receiver sees every value
+        assert_eq!(rx2.next_value().now_or_never(), Some(Some(1)));
+
+        let mut rx3 = tx.subscribe();
+
+        // `rx2` lags behind and skips `2`
+        tx.send(3);
+        tx.send(4);
+
+        assert_eq!(rx2.pending(), 2);
+        assert_eq!(rx2.next_value().now_or_never(), Some(Some(3)));
+        assert_eq!(rx3.next_value().now_or_never(), Some(Some(3)));
+
+        drop(tx);
+
+        assert_eq!(rx.next_value().now_or_never(), Some(Some(3)));
+        assert_eq!(rx.next_value().now_or_never(), Some(Some(4)));
+        assert_eq!(rx.next_value().now_or_never(), Some(None));
+        assert_eq!(rx2.next_value().now_or_never(), Some(Some(4)));
+        assert_eq!(rx2.next_value().now_or_never(), Some(None));
+    }
+
+    #[tokio::test]
+    async fn broadcast_wakes() {
+        let (tx, mut rx) = broadcast_channel(4);
+        let mut rx2 = rx.clone();
+
+        let tasks = [
+            tokio::spawn(async move { rx.next_value().await }),
+            tokio::spawn(async move { rx2.next_value().await }),
+        ];
+
+        tokio::task::yield_now().await;
+        tx.send("foo");
+
+        for task in tasks {
+            assert_eq!(task.await.unwrap(), Some("foo"));
+        }
+    }
+}
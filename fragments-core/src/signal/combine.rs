@@ -0,0 +1,193 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use pin_project::pin_project;
+
+use super::Signal;
+
+/// Combines the most recent values of multiple signals
+pub fn combine_latest<S: SignalTuple>(signals: S) -> CombineLatest<S> {
+    CombineLatest {
+        signals,
+        values: Default::default(),
+    }
+}
+
+/// The result of polling every signal in a [`SignalTuple`]
+#[derive(Debug, Clone, Copy)]
+pub struct PollValues {
+    /// At least one of the signals yielded a new value
+    pub changed: bool,
+    /// No more values can be produced, either since all signals are closed, or a signal closed
+    /// before yielding its first value
+    pub closed: bool,
+}
+
+/// A tuple of signals which can be combined into a tuple of their most recent values
+pub trait SignalTuple: Unpin {
+    /// The most recent value of each signal, if any
+    type Values: Default;
+    type Item;
+
+    /// Polls each signal once and stores the changed values
+    fn poll_values(&mut self, values: &mut Self::Values, cx: &mut Context<'_>) -> PollValues;
+
+    /// Returns the combined value if every signal has produced a value
+    fn get(values: &Self::Values) -> Option<Self::Item>;
+}
+
+/// Yields the most recent value of each signal whenever any of them change.
+///
+/// Does not yield until every signal has produced a value.
+#[pin_project]
+pub struct CombineLatest<S: SignalTuple> {
+    signals: S,
+    values: S::Values,
+}
+
+impl<'a, S> Signal<'a> for CombineLatest<S>
+where
+    S: SignalTuple,
+    S::Item: 'a,
+{
+    type Item = S::Item;
+
+    fn poll_changed(self: Pin<&'a mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let p = self.project();
+
+        loop {
+            let state = p.signals.poll_values(p.values, cx);
+
+            if state.closed {
+                return Poll::Ready(None);
+            }
+
+            if !state.changed {
+                return Poll::Pending;
+            }
+
+            if let Some(item) = S::get(p.values) {
+                return Poll::Ready(Some(item));
+            }
+
+            // Not every signal has a value yet, poll again so that all signals store a waker
+        }
+    }
+}
+
+macro_rules! tuple_impl {
+    ($($idx: tt => $ty: ident: $item: ident),*) => {
+        impl<$($ty, $item),*> SignalTuple for ($($ty,)*)
+        where
+            $($ty: Unpin + for<'x> Signal<'x, Item = $item>, $item: Clone,)*
+        {
+            type Values = ($(Option<$item>,)*);
+            type Item = ($($item,)*);
+
+            fn poll_values(&mut self, values: &mut Self::Values, cx: &mut Context<'_>) -> PollValues {
+                let mut changed = false;
+                let mut open = false;
+                let mut starved = false;
+
+                $(
+                    match Pin::new(&mut self.$idx).poll_changed(cx) {
+                        Poll::Ready(Some(value)) => {
+                            values.$idx = Some(value);
+                            changed = true;
+                            open = true;
+                        }
+                        Poll::Ready(None) => starved |= values.$idx.is_none(),
+                        Poll::Pending => open = true,
+                    }
+                )*
+
+                PollValues {
+                    changed,
+                    closed: !open || starved,
+                }
+            }
+
+            fn get(values: &Self::Values) -> Option<Self::Item> {
+                Some(($(values.$idx.clone()?,)*))
+            }
+        }
+    };
+}
+
+tuple_impl! { 0 => A: TA, 1 => B: TB }
+tuple_impl! { 0 => A: TA, 1 => B: TB, 2 => C: TC }
+tuple_impl! { 0 => A: TA, 1 => B: TB, 2 => C: TC, 3 => D: TD }
+tuple_impl! { 0 => A: TA, 1 => B: TB, 2 => C: TC, 3 => D: TD, 4 => E: TE }
+tuple_impl! { 0 => A: TA, 1 => B: TB, 2 => C: TC, 3 => D: TD, 4 => E: TE, 5 => F: TF }
+
+#[cfg(test)]
+mod test {
+    use futures::{stream, FutureExt};
+
+    use crate::signal::{from_future, from_stream, Mutable};
+
+    use super::*;
+
+    #[test]
+    fn zip() {
+        let a = Mutable::new(1);
+        let b = Mutable::new("foo");
+
+        let mut s = a.signal().zip(b.signal());
+
+        assert_eq!(s.next_value().now_or_never(), Some(Some((1, "foo"))));
+        assert_eq!(s.next_value().now_or_never(), None);
+
+        *a.write() = 2;
+        assert_eq!(s.next_value().now_or_never(), Some(Some((2, "foo"))));
+
+        *b.write() = "bar";
+        *a.write() = 3;
+        assert_eq!(s.next_value().now_or_never(), Some(Some((3, "bar"))));
+        assert_eq!(s.next_value().now_or_never(), None);
+
+        // The last value of `a` is kept
+        drop(a);
+        *b.write() = "baz";
+        assert_eq!(s.next_value().now_or_never(), Some(Some((3, "baz"))));
+
+        drop(b);
+        assert_eq!(s.next_value().now_or_never(), Some(None));
+    }
+
+    #[test]
+    fn combine_latest() {
+        let a = Mutable::new(1);
+        let b = Mutable::new(2);
+        let (tx, rx) = futures::channel::oneshot::channel();
+
+        let mut s = super::combine_latest((
+            a.signal(),
+            b.signal().map(|v| v * 10),
+            from_future(rx.map(Result::unwrap)),
+        ));
+
+        // Waiting for the third signal
+        assert_eq!(s.next_value().now_or_never(), None);
+        *a.write() = 3;
+        assert_eq!(s.next_value().now_or_never(), None);
+
+        tx.send(100).unwrap();
+        assert_eq!(s.next_value().now_or_never(), Some(Some((3, 20, 100))));
+
+        *b.write() = 4;
+        assert_eq!(s.next_value().now_or_never(), Some(Some((3, 40, 100))));
+    }
+
+    #[test]
+    fn combine_latest_starved() {
+        let a = Mutable::new(1);
+
+        // The second signal closes without ever producing a value
+        let mut s = super::combine_latest((a.signal(), from_stream(stream::empty::<i32>())));
+
+        assert_eq!(s.next_value().now_or_never(), Some(None));
+    }
+}
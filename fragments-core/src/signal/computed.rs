@@ -0,0 +1,330 @@
+use std::{
+    cell::RefCell,
+    fmt::Debug,
+    pin::Pin,
+    sync::{Arc, Weak},
+    task::{Context, Poll, Waker},
+};
+
+use futures::task::ArcWake;
+use parking_lot::Mutex;
+
+use super::{
+    waiter::{Hook, HookList},
+    Signal,
+};
+
+thread_local! {
+    static TRACKER: RefCell<Option<Tracker>> = RefCell::new(None);
+}
+
+/// Records the dependencies of the computation which is currently running
+struct Tracker {
+    hook: Arc<Hook>,
+    seen: Vec<*const HookList>,
+}
+
+/// Registers the currently running computation, if any, as dependent on `hooks`
+pub(crate) fn track(hooks: &HookList) {
+    TRACKER.with(|tracker| {
+        if let Some(tracker) = &mut *tracker.borrow_mut() {
+            let ptr = hooks as *const HookList;
+            if !tracker.seen.contains(&ptr) {
+                tracker.seen.push(ptr);
+                hooks.push(Arc::downgrade(&tracker.hook));
+            }
+        }
+    })
+}
+
+/// Runs `f` while recording every dependency read into `hook`
+fn with_tracker<R>(hook: Arc<Hook>, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<Tracker>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let prev = self.0.take();
+            TRACKER.with(|tracker| *tracker.borrow_mut() = prev);
+        }
+    }
+
+    let prev = TRACKER.with(|tracker| {
+        tracker.borrow_mut().replace(Tracker {
+            hook,
+            seen: Vec::new(),
+        })
+    });
+
+    let _restore = Restore(prev);
+    f()
+}
+
+/// Wakes the dependents of a computation when a dependency changes
+struct WakeDependents(Weak<HookList>);
+
+impl ArcWake for WakeDependents {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        if let Some(hooks) = arc_self.0.upgrade() {
+            hooks.wake_all()
+        }
+    }
+}
+
+struct State<T> {
+    value: Option<T>,
+    /// Incremented each time the computed value changes
+    version: u64,
+    /// Registered to each dependency read during the last computation
+    deps: Arc<Hook>,
+}
+
+struct ComputedInner<T> {
+    func: Mutex<Box<dyn FnMut() -> T + Send>>,
+    state: Mutex<State<T>>,
+    /// Invoked when a dependency changes
+    waker: Waker,
+    hooks: Arc<HookList>,
+}
+
+impl<T> ComputedInner<T>
+where
+    T: PartialEq,
+{
+    /// Recomputes the value if any dependency has changed since the last computation
+    fn update(&self, state: &mut State<T>) {
+        if state.value.is_some() && !state.deps.take_changed() {
+            return;
+        }
+
+        // Dropping the previous hook removes the dependencies of the last computation, as they
+        // may differ
+        let deps = Arc::new(Hook::new(false));
+        deps.set_waker(self.waker.clone());
+
+        let value = with_tracker(deps.clone(), || (self.func.lock())());
+        state.deps = deps;
+
+        if state.value.as_ref() != Some(&value) {
+            state.value = Some(value);
+            state.version += 1;
+        }
+    }
+}
+
+impl<T> Drop for ComputedInner<T> {
+    fn drop(&mut self) {
+        self.hooks.wake_all()
+    }
+}
+
+/// A value derived from other signals.
+///
+/// Every [`Mutable`](crate::signal::Mutable) or `Computed` read during the computation is
+/// recorded as a dependency. The value is lazily recomputed when a dependency changes, and the
+/// signals only fire when the computed value differs from the previous one.
+///
+/// Cyclic dependencies will deadlock.
+pub struct Computed<T> {
+    inner: Arc<ComputedInner<T>>,
+}
+
+impl<T> Computed<T>
+where
+    T: PartialEq,
+{
+    pub fn new(func: impl 'static + Send + FnMut() -> T) -> Self {
+        let hooks = Arc::new(HookList::default());
+
+        Self {
+            inner: Arc::new(ComputedInner {
+                func: Mutex::new(Box::new(func)),
+                state: Mutex::new(State {
+                    value: None,
+                    version: 0,
+                    deps: Arc::new(Hook::new(false)),
+                }),
+                waker: futures::task::waker(Arc::new(WakeDependents(Arc::downgrade(&hooks)))),
+                hooks,
+            }),
+        }
+    }
+
+    /// Returns the current value, recomputing it if necessary.
+    ///
+    /// Registers this as a dependency when called inside another `Computed`.
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        track(&self.inner.hooks);
+
+        let mut state = self.inner.state.lock();
+        self.inner.update(&mut state);
+        state.value.clone().unwrap()
+    }
+
+    pub fn signal(&self) -> ComputedSignal<T> {
+        let waiter = Arc::new(Hook::new(true));
+        self.inner.hooks.push(Arc::downgrade(&waiter));
+
+        ComputedSignal {
+            waiter,
+            version: 0,
+            state: Arc::downgrade(&self.inner),
+        }
+    }
+}
+
+impl<T> Clone for Computed<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Debug for Computed<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Computed")
+            .field("value", &self.inner.state.lock().value)
+            .finish()
+    }
+}
+
+/// Yields the value of a [`Computed`] each time it changes
+pub struct ComputedSignal<T> {
+    waiter: Arc<Hook>,
+    /// The last observed version
+    version: u64,
+    state: Weak<ComputedInner<T>>,
+}
+
+impl<'a, T> Signal<'a> for ComputedSignal<T>
+where
+    T: 'a + Clone + PartialEq,
+{
+    type Item = T;
+
+    fn poll_changed(mut self: Pin<&'a mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let Some(inner) = self.state.upgrade() else {
+            return Poll::Ready(None);
+        };
+
+        self.waiter.set_waker(cx.waker().clone());
+
+        // A dependency may have changed
+        if self.waiter.take_changed() {
+            let mut state = inner.state.lock();
+            inner.update(&mut state);
+
+            if state.version != self.version {
+                self.version = state.version;
+                return Poll::Ready(state.value.clone());
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use futures::FutureExt;
+
+    use crate::signal::Mutable;
+
+    use super::*;
+
+    #[test]
+    fn computed() {
+        let a = Mutable::new(1);
+        let b = Mutable::new(2);
+
+        let sum = Computed::new({
+            let a = a.clone();
+            let b = b.clone();
+            move || a.get() + b.get()
+        });
+
+        let mut signal = sum.signal();
+
+        assert_eq!(sum.get(), 3);
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(3)));
+        assert_eq!(signal.next_value().now_or_never(), None);
+
+        *a.write() = 5;
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(7)));
+
+        // Same result
+        *a.write() = 4;
+        *b.write() = 3;
+        assert_eq!(signal.next_value().now_or_never(), None);
+        assert_eq!(sum.get(), 7);
+
+        drop(sum);
+        assert_eq!(signal.next_value().now_or_never(), Some(None));
+    }
+
+    #[test]
+    fn dynamic_dependencies() {
+        let use_a = Mutable::new(true);
+        let a = Mutable::new(1);
+        let b = Mutable::new(2);
+
+        let value = Computed::new({
+            let (use_a, a, b) = (use_a.clone(), a.clone(), b.clone());
+            move || if use_a.get() { a.get() } else { b.get() }
+        });
+
+        let mut signal = value.signal();
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(1)));
+
+        *use_a.write() = false;
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(2)));
+
+        // No longer a dependency
+        *a.write() = 3;
+        assert!(!value.inner.state.lock().deps.take_changed());
+
+        *b.write() = 4;
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(4)));
+    }
+
+    #[test]
+    fn diamond() {
+        let computations = Arc::new(AtomicUsize::new(0));
+        let a = Mutable::new(1);
+
+        let b = Computed::new({
+            let a = a.clone();
+            move || a.get() * 2
+        });
+
+        let c = Computed::new({
+            let a = a.clone();
+            move || a.get() + 1
+        });
+
+        let d = Computed::new({
+            let computations = computations.clone();
+            move || {
+                computations.fetch_add(1, Ordering::Relaxed);
+                b.get() + c.get()
+            }
+        });
+
+        let mut signal = d.signal();
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(4)));
+        assert_eq!(computations.load(Ordering::Relaxed), 1);
+
+        *a.write() = 2;
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(7)));
+        assert_eq!(signal.next_value().now_or_never(), None);
+        assert_eq!(computations.load(Ordering::Relaxed), 2);
+    }
+}
@@ -0,0 +1,116 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::ready;
+use pin_project::pin_project;
+
+use super::Signal;
+
+/// Only yields the values which satisfy the predicate
+#[pin_project]
+pub struct Filter<S, F> {
+    #[pin]
+    pub(crate) signal: S,
+    pub(crate) f: F,
+}
+
+impl<'a, S, F, T> Signal<'a> for Filter<S, F>
+where
+    S: for<'x> Signal<'x, Item = T>,
+    F: FnMut(&T) -> bool,
+    T: 'a,
+{
+    type Item = T;
+
+    fn poll_changed(self: Pin<&'a mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut p = self.project();
+
+        loop {
+            match ready!(p.signal.as_mut().poll_changed(cx)) {
+                Some(v) if (p.f)(&v) => return Poll::Ready(Some(v)),
+                Some(_) => {}
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+/// Skips values which are equal to the previously yielded value
+#[pin_project]
+pub struct Dedupe<S, T> {
+    #[pin]
+    pub(crate) signal: S,
+    pub(crate) last: Option<T>,
+}
+
+impl<'a, S, T> Signal<'a> for Dedupe<S, T>
+where
+    S: for<'x> Signal<'x, Item = T>,
+    T: 'a + Clone + PartialEq,
+{
+    type Item = T;
+
+    fn poll_changed(self: Pin<&'a mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut p = self.project();
+
+        loop {
+            match ready!(p.signal.as_mut().poll_changed(cx)) {
+                Some(v) if p.last.as_ref() != Some(&v) => {
+                    *p.last = Some(v.clone());
+                    return Poll::Ready(Some(v));
+                }
+                Some(_) => {}
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use futures::FutureExt;
+
+    use crate::signal::Mutable;
+
+    use super::*;
+
+    #[test]
+    fn filter() {
+        let value = Mutable::new(1);
+
+        let mut s = value.signal().filter(|v| v % 2 == 0);
+
+        assert_eq!(s.next_value().now_or_never(), None);
+        *value.write() = 2;
+        assert_eq!(s.next_value().now_or_never(), Some(Some(2)));
+        *value.write() = 3;
+        assert_eq!(s.next_value().now_or_never(), None);
+        *value.write() = 4;
+        assert_eq!(s.next_value().now_or_never(), Some(Some(4)));
+
+        drop(value);
+        assert_eq!(s.next_value().now_or_never(), Some(None));
+    }
+
+    #[test]
+    fn dedupe() {
+        let value = Mutable::new("foo".to_string());
+
+        let mut s = value.signal_ref().map(|v| v.len()).dedupe();
+
+        assert_eq!(s.next_value().now_or_never(), Some(Some(3)));
+        *value.write() = "bar".to_string();
+        assert_eq!(s.next_value().now_or_never(), None);
+        *value.write() = "hello".to_string();
+        assert_eq!(s.next_value().now_or_never(), Some(Some(5)));
+        // Repeated writes without any change
+        value.write();
+        value.write();
+        assert_eq!(s.next_value().now_or_never(), None);
+
+        drop(value);
+        assert_eq!(s.next_value().now_or_never(), Some(None));
+    }
+}
@@ -0,0 +1,511 @@
+use std::{
+    collections::VecDeque,
+    fmt::Debug,
+    mem,
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
+
+use parking_lot::Mutex;
+
+use super::{batch, Mutable, MutableReadGuard, MutableSignal, MutableWriteGuard};
+
+/// A reversible change to a value, used to record changes without cloning the whole value
+pub trait HistoryOp<T>: Send {
+    fn apply(&mut self, value: &mut T);
+    fn revert(&mut self, value: &mut T);
+}
+
+/// A [`HistoryOp`] from a pair of closures
+pub struct FnOp<A, R> {
+    apply: A,
+    revert: R,
+}
+
+impl<A, R> FnOp<A, R> {
+    pub fn new<T>(apply: A, revert: R) -> Self
+    where
+        A: FnMut(&mut T),
+        R: FnMut(&mut T),
+    {
+        Self { apply, revert }
+    }
+}
+
+impl<T, A, R> HistoryOp<T> for FnOp<A, R>
+where
+    A: Send + FnMut(&mut T),
+    R: Send + FnMut(&mut T),
+{
+    fn apply(&mut self, value: &mut T) {
+        (self.apply)(value)
+    }
+
+    fn revert(&mut self, value: &mut T) {
+        (self.revert)(value)
+    }
+}
+
+enum Entry<T> {
+    /// The value before or after the change
+    Snapshot(T),
+    Op(Box<dyn HistoryOp<T>>),
+    /// Changes made within a transaction
+    Group(Vec<Entry<T>>),
+}
+
+impl<T> Entry<T> {
+    /// Reverts the change, and returns the entry which applies it again
+    fn revert(self, value: &mut T) -> Self {
+        match self {
+            Entry::Snapshot(v) => Entry::Snapshot(mem::replace(value, v)),
+            Entry::Op(mut op) => {
+                op.revert(value);
+                Entry::Op(op)
+            }
+            Entry::Group(entries) => {
+                let mut entries: Vec<_> = entries
+                    .into_iter()
+                    .rev()
+                    .map(|entry| entry.revert(value))
+                    .collect();
+
+                entries.reverse();
+                Entry::Group(entries)
+            }
+        }
+    }
+
+    /// Applies the change again, and returns the entry which reverts it
+    fn apply(self, value: &mut T) -> Self {
+        match self {
+            Entry::Snapshot(v) => Entry::Snapshot(mem::replace(value, v)),
+            Entry::Op(mut op) => {
+                op.apply(value);
+                Entry::Op(op)
+            }
+            Entry::Group(entries) => Entry::Group(
+                entries
+                    .into_iter()
+                    .map(|entry| entry.apply(value))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+struct State<T> {
+    undo: VecDeque<Entry<T>>,
+    redo: Vec<Entry<T>>,
+    max_depth: usize,
+    /// Changes recorded by the current transaction, and the nesting depth
+    transaction: Option<(Vec<Entry<T>>, usize)>,
+}
+
+struct HistoryInner<T> {
+    state: Mutex<State<T>>,
+    can_undo: Mutable<bool>,
+    can_redo: Mutable<bool>,
+}
+
+impl<T> HistoryInner<T> {
+    fn update_flags(&self, state: &State<T>) {
+        self.can_undo.set_if_changed(!state.undo.is_empty());
+        self.can_redo.set_if_changed(!state.redo.is_empty());
+    }
+
+    fn record(&self, entry: Entry<T>) {
+        let mut state = self.state.lock();
+
+        if let Some((entries, _)) = &mut state.transaction {
+            entries.push(entry);
+            return;
+        }
+
+        Self::push_undo(&mut state, entry);
+        state.redo.clear();
+
+        self.update_flags(&state);
+    }
+
+    fn push_undo(state: &mut State<T>, entry: Entry<T>) {
+        if state.max_depth == 0 {
+            return;
+        }
+
+        if state.undo.len() == state.max_depth {
+            state.undo.pop_front();
+        }
+
+        state.undo.push_back(entry);
+    }
+}
+
+/// Records the changes made to a [`Mutable`] so that they can be undone and redone.
+///
+/// Changes are recorded as snapshots of the previous value when written through [`Self::write`],
+/// or as user supplied operations through [`Self::apply`]. Writes made directly to the underlying
+/// mutable are not recorded.
+pub struct History<T> {
+    value: Mutable<T>,
+    inner: Arc<HistoryInner<T>>,
+}
+
+impl<T> History<T> {
+    /// Records the changes to `value`, retaining at most `max_depth` undo steps
+    pub fn new(value: Mutable<T>, max_depth: usize) -> Self {
+        Self {
+            value,
+            inner: Arc::new(HistoryInner {
+                state: Mutex::new(State {
+                    undo: VecDeque::new(),
+                    redo: Vec::new(),
+                    max_depth,
+                    transaction: None,
+                }),
+                can_undo: Mutable::new(false),
+                can_redo: Mutable::new(false),
+            }),
+        }
+    }
+
+    pub fn read(&self) -> MutableReadGuard<T> {
+        self.value.read()
+    }
+
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        self.value.get()
+    }
+
+    /// Locks the value for writing.
+    ///
+    /// The previous value is recorded when the guard is dropped, if it was mutably accessed.
+    pub fn write(&self) -> HistoryWriteGuard<T>
+    where
+        T: Clone,
+    {
+        let guard = self.value.write();
+        let prev = guard.clone();
+
+        HistoryWriteGuard {
+            guard: Some(guard),
+            prev: Some(prev),
+            inner: &self.inner,
+        }
+    }
+
+    pub fn set(&self, value: T)
+    where
+        T: Clone,
+    {
+        *self.write() = value;
+    }
+
+    /// Modifies the value in place and returns the result of `f`
+    pub fn update<R>(&self, f: impl FnOnce(&mut T) -> R) -> R
+    where
+        T: Clone,
+    {
+        f(&mut self.write())
+    }
+
+    /// Applies and records a reversible operation
+    pub fn apply(&self, mut op: impl 'static + HistoryOp<T>) {
+        op.apply(&mut self.value.write());
+        self.inner.record(Entry::Op(Box::new(op)));
+    }
+
+    /// Groups all changes made within `f` into a single undo step.
+    ///
+    /// Signals are notified once when the outermost transaction ends.
+    pub fn transaction<R>(&self, f: impl FnOnce(&Self) -> R) -> R {
+        struct Commit<'a, T>(&'a HistoryInner<T>);
+
+        impl<'a, T> Drop for Commit<'a, T> {
+            fn drop(&mut self) {
+                let mut state = self.0.state.lock();
+                let Some((_, depth)) = &mut state.transaction else {
+                    unreachable!("Transaction ended without being started")
+                };
+
+                *depth -= 1;
+                if *depth > 0 {
+                    return;
+                }
+
+                let (entries, _) = state.transaction.take().unwrap();
+                if !entries.is_empty() {
+                    HistoryInner::push_undo(&mut state, Entry::Group(entries));
+                    state.redo.clear();
+                }
+
+                self.0.update_flags(&state);
+            }
+        }
+
+        batch(|| {
+            {
+                let mut state = self.inner.state.lock();
+                match &mut state.transaction {
+                    Some((_, depth)) => *depth += 1,
+                    None => state.transaction = Some((Vec::new(), 1)),
+                }
+            }
+
+            let _commit = Commit(&self.inner);
+            f(self)
+        })
+    }
+
+    /// Reverts the most recent change.
+    ///
+    /// Returns false if there is nothing to undo, or if called within a transaction.
+    pub fn undo(&self) -> bool {
+        let mut state = self.inner.state.lock();
+        if state.transaction.is_some() {
+            tracing::warn!("Undo is not allowed within a transaction");
+            return false;
+        }
+
+        let Some(entry) = state.undo.pop_back() else {
+            return false;
+        };
+
+        let entry = entry.revert(&mut self.value.write());
+        state.redo.push(entry);
+
+        self.inner.update_flags(&state);
+        true
+    }
+
+    /// Applies the most recently undone change again.
+    ///
+    /// Returns false if there is nothing to redo, or if called within a transaction.
+    pub fn redo(&self) -> bool {
+        let mut state = self.inner.state.lock();
+        if state.transaction.is_some() {
+            tracing::warn!("Redo is not allowed within a transaction");
+            return false;
+        }
+
+        let Some(entry) = state.redo.pop() else {
+            return false;
+        };
+
+        let entry = entry.apply(&mut self.value.write());
+        HistoryInner::push_undo(&mut state, entry);
+
+        self.inner.update_flags(&state);
+        true
+    }
+
+    /// Discards all recorded changes
+    pub fn clear(&self) {
+        let mut state = self.inner.state.lock();
+        state.undo.clear();
+        state.redo.clear();
+        self.inner.update_flags(&state);
+    }
+
+    /// Returns a signal which yields whether there is a change to undo
+    pub fn can_undo(&self) -> MutableSignal<bool> {
+        self.inner.can_undo.signal()
+    }
+
+    /// Returns a signal which yields whether there is a change to redo
+    pub fn can_redo(&self) -> MutableSignal<bool> {
+        self.inner.can_redo.signal()
+    }
+
+    /// Returns the recorded mutable
+    pub fn mutable(&self) -> &Mutable<T> {
+        &self.value
+    }
+}
+
+impl<T> Clone for History<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Debug for History<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.inner.state.lock();
+        f.debug_struct("History")
+            .field("value", &self.value)
+            .field("undo", &state.undo.len())
+            .field("redo", &state.redo.len())
+            .finish()
+    }
+}
+
+pub struct HistoryWriteGuard<'a, T> {
+    guard: Option<MutableWriteGuard<'a, T>>,
+    prev: Option<T>,
+    inner: &'a HistoryInner<T>,
+}
+
+impl<'a, T> Deref for HistoryWriteGuard<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.guard.as_ref().unwrap()
+    }
+}
+
+impl<'a, T> DerefMut for HistoryWriteGuard<'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.guard.as_mut().unwrap()
+    }
+}
+
+impl<'a, T> Drop for HistoryWriteGuard<'a, T> {
+    fn drop(&mut self) {
+        let guard = self.guard.take().unwrap();
+        let modified = guard.is_modified();
+
+        // Release the lock before recording to not invert the lock order of `undo`
+        drop(guard);
+
+        if modified {
+            self.inner
+                .record(Entry::Snapshot(self.prev.take().unwrap()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use futures::FutureExt;
+
+    use crate::signal::Signal;
+
+    use super::*;
+
+    #[test]
+    fn undo_redo() {
+        let history = History::new(Mutable::new(String::new()), 16);
+        let mut can_undo = history.can_undo();
+        let mut can_redo = history.can_redo();
+
+        assert_eq!(can_undo.next_value().now_or_never(), Some(Some(false)));
+        assert_eq!(can_redo.next_value().now_or_never(), Some(Some(false)));
+
+        history.set("foo".into());
+        history.write().push_str("bar");
+
+        // Not modified
+        assert_eq!(*history.write(), "foobar");
+
+        assert_eq!(can_undo.next_value().now_or_never(), Some(Some(true)));
+
+        assert!(history.undo());
+        assert_eq!(history.get(), "foo");
+        assert_eq!(can_redo.next_value().now_or_never(), Some(Some(true)));
+
+        assert!(history.undo());
+        assert_eq!(history.get(), "");
+        assert!(!history.undo());
+        assert_eq!(can_undo.next_value().now_or_never(), Some(Some(false)));
+
+        assert!(history.redo());
+        assert!(history.redo());
+        assert_eq!(history.get(), "foobar");
+        assert!(!history.redo());
+
+        // A new change discards the redo stack
+        history.undo();
+        history.set("baz".into());
+        assert!(!history.redo());
+        assert_eq!(history.get(), "baz");
+    }
+
+    #[test]
+    fn operations() {
+        let history = History::new(Mutable::new(vec![1, 2]), 16);
+
+        history.apply(FnOp::new(
+            |v: &mut Vec<i32>| v.push(3),
+            |v| {
+                v.pop();
+            },
+        ));
+        assert_eq!(history.get(), [1, 2, 3]);
+
+        history.undo();
+        assert_eq!(history.get(), [1, 2]);
+
+        history.redo();
+        assert_eq!(history.get(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn transaction() {
+        let history = History::new(Mutable::new(0), 16);
+        let mut signal = history.mutable().signal();
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(0)));
+
+        history.transaction(|history| {
+            history.set(1);
+            history.transaction(|history| history.update(|v| *v *= 10));
+            history.apply(FnOp::new(|v: &mut i32| *v += 5, |v| *v -= 5));
+        });
+
+        assert_eq!(history.get(), 15);
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(15)));
+
+        // Undone as a single step
+        assert!(history.undo());
+        assert_eq!(history.get(), 0);
+        assert!(!history.undo());
+
+        assert!(history.redo());
+        assert_eq!(history.get(), 15);
+    }
+
+    #[test]
+    fn undo_in_transaction() {
+        let history = History::new(Mutable::new(0), 16);
+        history.set(1);
+        history.set(2);
+        assert!(history.undo());
+
+        history.transaction(|history| {
+            history.set(3);
+
+            // Ignored until the transaction has ended
+            assert!(!history.undo());
+            assert!(!history.redo());
+            assert_eq!(history.get(), 3);
+        });
+
+        assert!(history.undo());
+        assert_eq!(history.get(), 1);
+        assert!(history.redo());
+        assert_eq!(history.get(), 3);
+    }
+
+    #[test]
+    fn max_depth() {
+        let history = History::new(Mutable::new(0), 2);
+
+        for i in 1..=4 {
+            history.set(i);
+        }
+
+        assert!(history.undo());
+        assert!(history.undo());
+        assert!(!history.undo());
+        assert_eq!(history.get(), 2);
+    }
+}
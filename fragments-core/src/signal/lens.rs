@@ -0,0 +1,231 @@
+use std::{
+    fmt::Debug,
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
+
+use super::{Dedupe, Map, Mutable, MutableReadGuard, MutableSignalRef, MutableWriteGuard, Signal};
+
+type Getter<T, U> = Arc<dyn Fn(&T) -> &U + Send + Sync>;
+type GetterMut<T, U> = Arc<dyn Fn(&mut T) -> &mut U + Send + Sync>;
+
+/// Signal of a projected field which only fires when the field changes
+pub type LensSignal<T, U> = Dedupe<
+    Map<MutableSignalRef<T>, Box<dyn for<'x> FnMut(MutableReadGuard<'x, T>) -> U + Send>>,
+    U,
+>;
+
+/// Reads and writes a part of the value of a [`Mutable`].
+///
+/// Writes notify all signals of the mutable, but the signal of the lens only fires when the
+/// projected part changes.
+pub struct Lens<T, U> {
+    mutable: Mutable<T>,
+    get: Getter<T, U>,
+    get_mut: GetterMut<T, U>,
+}
+
+impl<T> Mutable<T> {
+    /// Creates a lens focusing on a part of the value
+    pub fn lens<U>(
+        &self,
+        get: impl 'static + Send + Sync + Fn(&T) -> &U,
+        get_mut: impl 'static + Send + Sync + Fn(&mut T) -> &mut U,
+    ) -> Lens<T, U> {
+        Lens {
+            mutable: self.clone(),
+            get: Arc::new(get),
+            get_mut: Arc::new(get_mut),
+        }
+    }
+}
+
+impl<T, U> Lens<T, U> {
+    pub fn read(&self) -> LensReadGuard<T, U> {
+        LensReadGuard {
+            guard: self.mutable.read(),
+            get: &*self.get,
+        }
+    }
+
+    /// Returns a clone of the projected value
+    pub fn get(&self) -> U
+    where
+        U: Clone,
+    {
+        self.read().clone()
+    }
+
+    /// Locks the value for writing.
+    ///
+    /// The signals are notified when the guard is dropped, but only if the projected value was
+    /// mutably accessed.
+    pub fn write(&self) -> LensWriteGuard<T, U> {
+        LensWriteGuard {
+            guard: self.mutable.write(),
+            get: &*self.get,
+            get_mut: &*self.get_mut,
+        }
+    }
+
+    pub fn set(&self, value: U) {
+        *self.write() = value;
+    }
+
+    /// Sets a new value, only notifying the signals if it differs from the current value.
+    ///
+    /// Returns true if the value was changed.
+    pub fn set_if_changed(&self, value: U) -> bool
+    where
+        U: PartialEq,
+    {
+        let mut guard = self.write();
+        if *guard != value {
+            *guard = value;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Modifies the projected value in place and returns the result of `f`
+    pub fn update<R>(&self, f: impl FnOnce(&mut U) -> R) -> R {
+        f(&mut self.write())
+    }
+
+    /// Returns a signal which yields the projected value each time it changes
+    pub fn signal(&self) -> LensSignal<T, U>
+    where
+        T: 'static,
+        U: 'static + Clone + PartialEq,
+    {
+        let get = self.get.clone();
+
+        let f: Box<dyn for<'x> FnMut(MutableReadGuard<'x, T>) -> U + Send> =
+            Box::new(move |v| get(&v).clone());
+
+        self.mutable.signal_ref().map(f).dedupe()
+    }
+
+    /// Returns the mutable which the lens projects
+    pub fn mutable(&self) -> &Mutable<T> {
+        &self.mutable
+    }
+}
+
+impl<T, U> Clone for Lens<T, U> {
+    fn clone(&self) -> Self {
+        Self {
+            mutable: self.mutable.clone(),
+            get: self.get.clone(),
+            get_mut: self.get_mut.clone(),
+        }
+    }
+}
+
+impl<T, U> Debug for Lens<T, U>
+where
+    U: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Lens")
+            .field("value", &*self.read())
+            .finish()
+    }
+}
+
+pub struct LensReadGuard<'a, T, U> {
+    guard: MutableReadGuard<'a, T>,
+    get: &'a (dyn Fn(&T) -> &U + Send + Sync),
+}
+
+impl<'a, T, U> Deref for LensReadGuard<'a, T, U> {
+    type Target = U;
+
+    fn deref(&self) -> &Self::Target {
+        (self.get)(&self.guard)
+    }
+}
+
+pub struct LensWriteGuard<'a, T, U> {
+    guard: MutableWriteGuard<'a, T>,
+    get: &'a (dyn Fn(&T) -> &U + Send + Sync),
+    get_mut: &'a (dyn Fn(&mut T) -> &mut U + Send + Sync),
+}
+
+impl<'a, T, U> Deref for LensWriteGuard<'a, T, U> {
+    type Target = U;
+
+    fn deref(&self) -> &Self::Target {
+        (self.get)(&self.guard)
+    }
+}
+
+impl<'a, T, U> DerefMut for LensWriteGuard<'a, T, U> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        (self.get_mut)(&mut self.guard)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use futures::FutureExt;
+
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct State {
+        count: i32,
+        name: String,
+    }
+
+    #[test]
+    fn lens() {
+        let state = Mutable::new(State {
+            count: 0,
+            name: "foo".into(),
+        });
+
+        let name = state.lens(|v| &v.name, |v| &mut v.name);
+        let mut name_signal = name.signal();
+        let mut state_signal = state.signal();
+
+        assert_eq!(
+            name_signal.next_value().now_or_never(),
+            Some(Some("foo".into()))
+        );
+        assert!(state_signal.next_value().now_or_never().is_some());
+
+        // Changing another field
+        state.write().count += 1;
+        assert_eq!(name_signal.next_value().now_or_never(), None);
+        assert!(state_signal.next_value().now_or_never().is_some());
+
+        name.write().push_str("bar");
+        assert_eq!(&*name.read(), "foobar");
+        assert_eq!(
+            name_signal.next_value().now_or_never(),
+            Some(Some("foobar".into()))
+        );
+        assert_eq!(
+            state_signal.next_value().now_or_never(),
+            Some(Some(State {
+                count: 1,
+                name: "foobar".into(),
+            }))
+        );
+
+        assert!(!name.set_if_changed("foobar".into()));
+        assert_eq!(state_signal.next_value().now_or_never(), None);
+
+        name.set("baz".into());
+        assert_eq!(name.get(), "baz");
+        assert_eq!(
+            name_signal.next_value().now_or_never(),
+            Some(Some("baz".into()))
+        );
+
+        drop((state, name));
+        assert_eq!(name_signal.next_value().now_or_never(), Some(None));
+    }
+}
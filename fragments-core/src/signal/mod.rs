@@ -1,17 +1,47 @@
+mod batch;
+mod broadcast;
+mod combine;
+mod computed;
+mod filter;
+mod history;
 pub mod hold;
+mod lens;
 mod map;
 mod mutable;
+mod mutable_map;
+mod mutable_vec;
 mod notify;
+mod queue;
+#[cfg(feature = "serde")]
+mod store;
+mod switch;
+mod timing;
 mod waiter;
 
+pub use batch::*;
+pub use broadcast::*;
+pub use combine::*;
+pub use computed::*;
+pub use filter::*;
+pub use history::*;
+pub use lens::*;
 pub use map::*;
 pub use mutable::*;
+pub use mutable_map::*;
+pub use mutable_vec::*;
+pub use notify::*;
 use pin_project::pin_project;
+pub use queue::*;
+#[cfg(feature = "serde")]
+pub use store::*;
+pub use switch::*;
+pub use timing::*;
 
 use std::{
     ops::{Deref, DerefMut},
     pin::Pin,
     task::{Context, Poll},
+    time::Duration,
 };
 
 use futures::{ready, Future, Stream, StreamExt};
@@ -46,6 +76,102 @@ pub trait Signal<'a> {
         Map { signal: self, f }
     }
 
+    /// Only yield the values which satisfy the predicate
+    fn filter<F>(self, f: F) -> Filter<Self, F>
+    where
+        Self: Sized,
+        F: for<'x> FnMut(&<Self as Signal<'x>>::Item) -> bool,
+    {
+        Filter { signal: self, f }
+    }
+
+    /// Skip values which are equal to the previously yielded value
+    #[doc(alias = "distinct_until_changed")]
+    fn dedupe(self) -> Dedupe<Self, Self::Item>
+    where
+        Self: Sized,
+    {
+        Dedupe {
+            signal: self,
+            last: None,
+        }
+    }
+
+    /// Combine the most recent values of two signals.
+    ///
+    /// See [`combine_latest`] for combining more signals.
+    fn zip<S>(self, other: S) -> CombineLatest<(Self, S)>
+    where
+        Self: Sized,
+        (Self, S): SignalTuple,
+    {
+        combine_latest((self, other))
+    }
+
+    /// Flattens a signal of signals by yielding the values of the most recent inner signal
+    fn switch(self) -> Switch<Self, Self::Item>
+    where
+        Self: Sized,
+    {
+        Switch::new(self)
+    }
+
+    /// Maps each value to a signal, and yields the values of the most recent one
+    #[doc(alias = "flat_map")]
+    fn switch_map<F, I>(self, f: F) -> Switch<Map<Self, F>, I>
+    where
+        Self: Sized,
+        F: for<'x> FnMut(<Self as Signal<'x>>::Item) -> I,
+    {
+        Switch::new(Map { signal: self, f })
+    }
+
+    /// Yields a value only once no new value has arrived for `duration`.
+    ///
+    /// The pending value is yielded immediately if the signal is closed.
+    fn debounce(self, duration: Duration) -> Debounce<Self, Self::Item>
+    where
+        Self: Sized,
+    {
+        Debounce::new(self, duration)
+    }
+
+    /// Yields at most one value per `duration`.
+    ///
+    /// The first value is yielded immediately, and the most recent value received during the
+    /// following window is yielded when the window ends.
+    fn throttle(self, duration: Duration) -> Throttle<Self, Self::Item>
+    where
+        Self: Sized,
+    {
+        Throttle::new(self, duration)
+    }
+
+    /// Yields each value `duration` after it was received
+    fn delay(self, duration: Duration) -> Delay<Self, Self::Item>
+    where
+        Self: Sized,
+    {
+        Delay::new(self, duration)
+    }
+
+    /// Yields the most recent value every `period`, if it has changed since the last sample
+    fn sample(self, period: Duration) -> Sample<Self, Self::Item>
+    where
+        Self: Sized,
+    {
+        Sample::new(self, period)
+    }
+
+    /// Executes `f` for each value until the signal is closed
+    fn for_each<F>(self, f: F) -> ForEach<Self, F>
+    where
+        Self: Sized,
+        F: for<'x> FnMut(<Self as Signal<'x>>::Item),
+    {
+        ForEach { signal: self, f }
+    }
+
     fn by_ref(&mut self) -> &mut Self {
         self
     }
@@ -222,6 +348,31 @@ where
     }
 }
 
+/// Future which executes a function for each value of a signal
+#[pin_project]
+pub struct ForEach<S, F> {
+    #[pin]
+    signal: S,
+    f: F,
+}
+
+impl<S, F> Future for ForEach<S, F>
+where
+    S: for<'x> Signal<'x>,
+    F: for<'x> FnMut(<S as Signal<'x>>::Item),
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut p = self.project();
+        while let Some(item) = ready!(p.signal.as_mut().poll_changed(cx)) {
+            (p.f)(item)
+        }
+
+        Poll::Ready(())
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -274,4 +425,24 @@ mod test {
 
         assert_eq!(signal.next_value().await, Some("Hello, World!"))
     }
+
+    #[tokio::test]
+    async fn for_each() {
+        let value = Mutable::new("foo".to_string());
+
+        let (tx, rx) = futures::channel::mpsc::unbounded();
+        let task = tokio::spawn(
+            value
+                .signal_ref()
+                .for_each(move |v| tx.unbounded_send(v.to_uppercase()).unwrap()),
+        );
+
+        tokio::task::yield_now().await;
+        *value.write() = "bar".to_string();
+        tokio::task::yield_now().await;
+        drop(value);
+
+        task.await.unwrap();
+        assert_eq!(rx.collect::<Vec<_>>().await, ["FOO", "BAR"]);
+    }
 }
@@ -1,5 +1,6 @@
 use std::{
     fmt::Debug,
+    mem,
     pin::Pin,
     sync::{
         atomic::{AtomicUsize, Ordering},
@@ -11,6 +12,7 @@ use std::{
 use parking_lot::{self, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 use super::{
+    computed::track,
     waiter::{Hook, HookList},
     Signal,
 };
@@ -30,18 +32,84 @@ impl<T> Mutable<T> {
         }
     }
 
+    /// Locks the value for reading.
+    ///
+    /// Registers the mutable as a dependency when called inside a [`Computed`](super::Computed).
+    pub fn read(&self) -> MutableReadGuard<T> {
+        track(&self.inner.hooks);
+
+        MutableReadGuard {
+            value: self.inner.value.read(),
+        }
+    }
+
+    /// Returns a clone of the current value.
+    ///
+    /// Registers the mutable as a dependency when called inside a [`Computed`](super::Computed).
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        self.read().clone()
+    }
+
+    /// Locks the value for writing.
+    ///
+    /// The signals are notified when the guard is dropped, but only if the value was mutably
+    /// accessed.
     pub fn write(&self) -> MutableWriteGuard<T> {
+        self.write_inner(Some(&self.inner.hooks))
+    }
+
+    /// Locks the value for writing without notifying the signals
+    pub fn write_silent(&self) -> MutableWriteGuard<T> {
+        self.write_inner(None)
+    }
+
+    fn write_inner<'a>(&'a self, waiters: Option<&'a HookList>) -> MutableWriteGuard<'a, T> {
         let value = self.inner.value.write();
 
         let wake_on_drop = WakeOnDrop {
-            waiters: &self.inner.hooks,
+            waiters,
+            modified: false,
         };
 
         MutableWriteGuard {
             value,
-            _wake_on_drop: wake_on_drop,
+            wake_on_drop,
         }
     }
+
+    /// Sets a new value
+    pub fn set(&self, value: T) {
+        *self.write() = value;
+    }
+
+    /// Sets a new value, only notifying the signals if it differs from the current value.
+    ///
+    /// Returns true if the value was changed.
+    pub fn set_if_changed(&self, value: T) -> bool
+    where
+        T: PartialEq,
+    {
+        let mut guard = self.write();
+        if *guard != value {
+            *guard = value;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Modifies the value in place and returns the result of `f`
+    pub fn update<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        f(&mut self.write())
+    }
+
+    /// Replaces the value, returning the previous value
+    pub fn replace(&self, value: T) -> T {
+        mem::replace(&mut *self.write(), value)
+    }
 }
 
 impl<T> Drop for Mutable<T> {
@@ -74,18 +142,29 @@ where
 }
 
 struct WakeOnDrop<'a> {
-    waiters: &'a HookList,
+    waiters: Option<&'a HookList>,
+    modified: bool,
 }
 
 impl<'a> Drop for WakeOnDrop<'a> {
     fn drop(&mut self) {
-        self.waiters.wake_all()
+        if let Some(waiters) = self.waiters.filter(|_| self.modified) {
+            waiters.wake_all()
+        }
     }
 }
 
 pub struct MutableWriteGuard<'a, T> {
     value: RwLockWriteGuard<'a, T>,
-    _wake_on_drop: WakeOnDrop<'a>,
+    // Dropped after the lock is released
+    wake_on_drop: WakeOnDrop<'a>,
+}
+
+impl<'a, T> MutableWriteGuard<'a, T> {
+    /// Returns true if the value has been mutably accessed through the guard
+    pub fn is_modified(&self) -> bool {
+        self.wake_on_drop.modified
+    }
 }
 
 impl<'a, T> std::ops::Deref for MutableWriteGuard<'a, T> {
@@ -98,6 +177,7 @@ impl<'a, T> std::ops::Deref for MutableWriteGuard<'a, T> {
 
 impl<'a, T> std::ops::DerefMut for MutableWriteGuard<'a, T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
+        self.wake_on_drop.modified = true;
         &mut self.value
     }
 }
@@ -145,7 +225,7 @@ impl<T> Mutable<T> {
         }
     }
 
-    fn push_waiter(&self, waiter: Weak<Hook>) {
+    pub(crate) fn push_waiter(&self, waiter: Weak<Hook>) {
         self.inner.hooks.push(waiter)
     }
 }
@@ -258,4 +338,40 @@ mod test {
         eprintln!("Joining task");
         assert!(task.now_or_never().is_some());
     }
+
+    #[test]
+    fn change_aware_writes() {
+        let value = Mutable::new(1);
+
+        let mut signal = value.signal();
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(1)));
+
+        // Not modified
+        assert_eq!(*value.write(), 1);
+        assert_eq!(signal.next_value().now_or_never(), None);
+
+        assert!(!value.set_if_changed(1));
+        assert_eq!(signal.next_value().now_or_never(), None);
+
+        assert!(value.set_if_changed(2));
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(2)));
+
+        *value.write_silent() = 3;
+        assert_eq!(signal.next_value().now_or_never(), None);
+
+        assert_eq!(value.replace(4), 3);
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(4)));
+
+        assert_eq!(
+            value.update(|v| {
+                *v *= 2;
+                *v + 1
+            }),
+            9
+        );
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(8)));
+
+        value.set(8);
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(8)));
+    }
 }
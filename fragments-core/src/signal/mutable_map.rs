@@ -0,0 +1,335 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Debug,
+    ops::Deref,
+    pin::Pin,
+    sync::{Arc, Weak},
+    task::{Context, Poll},
+};
+
+use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use super::{
+    waiter::{DiffHook, HookList},
+    Signal,
+};
+
+/// Describes an incremental change to a [`MutableMap`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MapDiff<K, V> {
+    Insert { key: K, value: V },
+    Update { key: K, value: V },
+    Remove { key: K },
+    Replace { entries: Vec<(K, V)> },
+    Clear,
+}
+
+impl<K: Ord, V> MapDiff<K, V> {
+    /// Applies the change to a map
+    pub fn apply(self, map: &mut BTreeMap<K, V>) {
+        match self {
+            MapDiff::Insert { key, value } | MapDiff::Update { key, value } => {
+                map.insert(key, value);
+            }
+            MapDiff::Remove { key } => {
+                map.remove(&key);
+            }
+            MapDiff::Replace { entries } => *map = entries.into_iter().collect(),
+            MapDiff::Clear => map.clear(),
+        }
+    }
+}
+
+impl<K, V> MapDiff<K, V> {
+    /// Returns true if the change discards all previous state
+    fn is_reset(&self) -> bool {
+        matches!(self, MapDiff::Replace { .. } | MapDiff::Clear)
+    }
+}
+
+struct MutableMapInner<K, V> {
+    map: RwLock<BTreeMap<K, V>>,
+    hooks: HookList<DiffHook<MapDiff<K, V>>>,
+}
+
+impl<K, V> Drop for MutableMapInner<K, V> {
+    fn drop(&mut self) {
+        // Notify the signals that they are closed
+        self.hooks.for_each(|hook| hook.wake())
+    }
+}
+
+/// An ordered map which can be observed through incremental changes
+pub struct MutableMap<K, V> {
+    inner: Arc<MutableMapInner<K, V>>,
+}
+
+impl<K, V> MutableMap<K, V> {
+    pub fn new(map: BTreeMap<K, V>) -> Self {
+        Self {
+            inner: Arc::new(MutableMapInner {
+                map: RwLock::new(map),
+                hooks: Default::default(),
+            }),
+        }
+    }
+
+    pub fn read(&self) -> MutableMapReadGuard<K, V> {
+        MutableMapReadGuard {
+            map: self.inner.map.read(),
+        }
+    }
+
+    /// Lock the map for modification.
+    ///
+    /// The changes are sent to the signals as a single batch when the guard is dropped.
+    pub fn write(&self) -> MutableMapWriteGuard<K, V>
+    where
+        K: Ord + Clone,
+        V: Clone,
+    {
+        MutableMapWriteGuard {
+            map: Some(self.inner.map.write()),
+            hooks: &self.inner.hooks,
+            diffs: Vec::new(),
+        }
+    }
+
+    /// Returns a signal which yields the changes to the map since it was last polled.
+    ///
+    /// The first batch contains the current entries.
+    pub fn signal(&self) -> MutableMapSignal<K, V>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        // Prevent any writes between the initial value and registration
+        let map = self.inner.map.read();
+
+        let entries = map.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+        let hook = Arc::new(DiffHook::new(vec![MapDiff::Replace { entries }]));
+
+        self.inner.hooks.push(Arc::downgrade(&hook));
+
+        MutableMapSignal {
+            hook,
+            state: Arc::downgrade(&self.inner),
+        }
+    }
+}
+
+impl<K, V> Default for MutableMap<K, V> {
+    fn default() -> Self {
+        Self::new(BTreeMap::new())
+    }
+}
+
+impl<K, V> Clone for MutableMap<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<K, V> Debug for MutableMap<K, V>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MutableMap")
+            .field("map", &self.inner.map)
+            .finish()
+    }
+}
+
+pub struct MutableMapReadGuard<'a, K, V> {
+    map: RwLockReadGuard<'a, BTreeMap<K, V>>,
+}
+
+impl<'a, K, V> Deref for MutableMapReadGuard<'a, K, V> {
+    type Target = BTreeMap<K, V>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.map
+    }
+}
+
+/// Allows modifying a [`MutableMap`] while recording the changes
+pub struct MutableMapWriteGuard<'a, K: Ord + Clone, V: Clone> {
+    /// Released before the changes are sent
+    map: Option<RwLockWriteGuard<'a, BTreeMap<K, V>>>,
+    hooks: &'a HookList<DiffHook<MapDiff<K, V>>>,
+    diffs: Vec<MapDiff<K, V>>,
+}
+
+impl<'a, K: Ord + Clone, V: Clone> MutableMapWriteGuard<'a, K, V> {
+    fn map(&mut self) -> &mut BTreeMap<K, V> {
+        self.map.as_mut().unwrap()
+    }
+
+    /// Inserts a value, returning the previous value
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let old = self.map().insert(key.clone(), value.clone());
+
+        if old.is_some() {
+            self.diffs.push(MapDiff::Update { key, value });
+        } else {
+            self.diffs.push(MapDiff::Insert { key, value });
+        }
+
+        old
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let old = self.map().remove(key)?;
+        self.diffs.push(MapDiff::Remove { key: key.clone() });
+        Some(old)
+    }
+
+    pub fn replace(&mut self, map: BTreeMap<K, V>) {
+        let entries = map.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+        *self.map() = map;
+        self.diffs.push(MapDiff::Replace { entries });
+    }
+
+    pub fn clear(&mut self) {
+        self.map().clear();
+        self.diffs.push(MapDiff::Clear);
+    }
+}
+
+impl<'a, K: Ord + Clone, V: Clone> Deref for MutableMapWriteGuard<'a, K, V> {
+    type Target = BTreeMap<K, V>;
+
+    fn deref(&self) -> &Self::Target {
+        self.map.as_ref().unwrap()
+    }
+}
+
+impl<'a, K: Ord + Clone, V: Clone> Drop for MutableMapWriteGuard<'a, K, V> {
+    fn drop(&mut self) {
+        // Release the lock first, as the signals may read the map as soon as they are woken
+        drop(self.map.take());
+
+        if self.diffs.is_empty() {
+            return;
+        }
+
+        // Changes before a reset are redundant
+        let start = self.diffs.iter().rposition(MapDiff::is_reset);
+        let diffs = &self.diffs[start.unwrap_or(0)..];
+
+        self.hooks
+            .for_each(|hook| hook.send(diffs.iter().cloned(), start.is_some()));
+    }
+}
+
+/// Yields batches of changes to a [`MutableMap`]
+pub struct MutableMapSignal<K, V> {
+    hook: Arc<DiffHook<MapDiff<K, V>>>,
+    state: Weak<MutableMapInner<K, V>>,
+}
+
+impl<'a, K, V> Signal<'a> for MutableMapSignal<K, V>
+where
+    K: 'a,
+    V: 'a,
+{
+    type Item = Vec<MapDiff<K, V>>;
+
+    fn poll_changed(self: Pin<&'a mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match self.hook.poll_diffs(cx) {
+            Poll::Ready(diffs) => Poll::Ready(Some(diffs)),
+            Poll::Pending if self.state.strong_count() == 0 => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use futures::FutureExt;
+
+    use super::*;
+
+    #[test]
+    fn mutable_map() {
+        let map = MutableMap::new(BTreeMap::from([("a", 1), ("b", 2)]));
+
+        let mut signal = map.signal();
+
+        assert_eq!(
+            signal.next_value().now_or_never(),
+            Some(Some(vec![MapDiff::Replace {
+                entries: vec![("a", 1), ("b", 2)]
+            }]))
+        );
+
+        {
+            let mut map = map.write();
+            map.insert("c", 3);
+            map.insert("a", 4);
+            map.remove(&"b");
+            // Not present
+            map.remove(&"d");
+        }
+
+        let diffs = signal.next_value().now_or_never().unwrap().unwrap();
+        assert_eq!(
+            diffs,
+            [
+                MapDiff::Insert { key: "c", value: 3 },
+                MapDiff::Update { key: "a", value: 4 },
+                MapDiff::Remove { key: "b" },
+            ]
+        );
+
+        let mut mirror = BTreeMap::from([("a", 1), ("b", 2)]);
+        diffs.into_iter().for_each(|v| v.apply(&mut mirror));
+        assert_eq!(mirror, *map.read());
+
+        map.write().clear();
+        map.write().insert("e", 5);
+
+        assert_eq!(
+            signal.next_value().now_or_never(),
+            Some(Some(vec![
+                MapDiff::Clear,
+                MapDiff::Insert { key: "e", value: 5 }
+            ]))
+        );
+
+        drop(map);
+        assert_eq!(signal.next_value().now_or_never(), Some(None));
+    }
+
+    #[test]
+    fn wake_after_unlock() {
+        let values = MutableMap::new(BTreeMap::from([("a", 1)]));
+        let mut signal = values.signal();
+        assert!(signal.next_value().now_or_never().is_some());
+
+        // A waker which reads the values right away must not deadlock
+        let unlocked = Arc::new(AtomicBool::new(false));
+        let waker = waker_fn::waker_fn({
+            let values = values.clone();
+            let unlocked = unlocked.clone();
+            move || {
+                let is_unlocked = values.inner.map.try_read().is_some();
+                unlocked.store(is_unlocked, Ordering::SeqCst);
+            }
+        });
+
+        assert!(signal
+            .next_value()
+            .poll_unpin(&mut Context::from_waker(&waker))
+            .is_pending());
+
+        values.write().insert("b", 2);
+        assert!(unlocked.load(Ordering::SeqCst));
+    }
+}
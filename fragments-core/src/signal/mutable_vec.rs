@@ -0,0 +1,425 @@
+use std::{
+    fmt::Debug,
+    ops::Deref,
+    pin::Pin,
+    sync::{Arc, Weak},
+    task::{Context, Poll},
+};
+
+use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use super::{
+    waiter::{DiffHook, HookList},
+    Signal,
+};
+
+/// Describes an incremental change to a [`MutableVec`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VecDiff<T> {
+    Push {
+        value: T,
+    },
+    Insert {
+        index: usize,
+        value: T,
+    },
+    Remove {
+        index: usize,
+    },
+    Update {
+        index: usize,
+        value: T,
+    },
+    /// Removes the item at `from` and inserts it at `to`
+    Move {
+        from: usize,
+        to: usize,
+    },
+    Replace {
+        values: Vec<T>,
+    },
+    Clear,
+}
+
+impl<T> VecDiff<T> {
+    /// Applies the change to a vec
+    pub fn apply(self, values: &mut Vec<T>) {
+        match self {
+            VecDiff::Push { value } => values.push(value),
+            VecDiff::Insert { index, value } => values.insert(index, value),
+            VecDiff::Remove { index } => {
+                values.remove(index);
+            }
+            VecDiff::Update { index, value } => values[index] = value,
+            VecDiff::Move { from, to } => {
+                let value = values.remove(from);
+                values.insert(to, value);
+            }
+            VecDiff::Replace { values: new_values } => *values = new_values,
+            VecDiff::Clear => values.clear(),
+        }
+    }
+
+    /// Returns true if the change discards all previous state
+    fn is_reset(&self) -> bool {
+        matches!(self, VecDiff::Replace { .. } | VecDiff::Clear)
+    }
+}
+
+struct MutableVecInner<T> {
+    values: RwLock<Vec<T>>,
+    hooks: HookList<DiffHook<VecDiff<T>>>,
+}
+
+impl<T> Drop for MutableVecInner<T> {
+    fn drop(&mut self) {
+        // Notify the signals that they are closed
+        self.hooks.for_each(|hook| hook.wake())
+    }
+}
+
+/// A vec which can be observed through incremental changes
+pub struct MutableVec<T> {
+    inner: Arc<MutableVecInner<T>>,
+}
+
+impl<T> MutableVec<T> {
+    pub fn new(values: Vec<T>) -> Self {
+        Self {
+            inner: Arc::new(MutableVecInner {
+                values: RwLock::new(values),
+                hooks: Default::default(),
+            }),
+        }
+    }
+
+    pub fn read(&self) -> MutableVecReadGuard<T> {
+        MutableVecReadGuard {
+            values: self.inner.values.read(),
+        }
+    }
+
+    /// Lock the vec for modification.
+    ///
+    /// The changes are sent to the signals as a single batch when the guard is dropped.
+    pub fn write(&self) -> MutableVecWriteGuard<T>
+    where
+        T: Clone,
+    {
+        MutableVecWriteGuard {
+            values: Some(self.inner.values.write()),
+            hooks: &self.inner.hooks,
+            diffs: Vec::new(),
+        }
+    }
+
+    /// Returns a signal which yields the changes to the vec since it was last polled.
+    ///
+    /// The first batch contains the current values.
+    pub fn signal(&self) -> MutableVecSignal<T>
+    where
+        T: Clone,
+    {
+        // Prevent any writes between the initial value and registration
+        let values = self.inner.values.read();
+
+        let hook = Arc::new(DiffHook::new(vec![VecDiff::Replace {
+            values: values.clone(),
+        }]));
+
+        self.inner.hooks.push(Arc::downgrade(&hook));
+
+        MutableVecSignal {
+            hook,
+            state: Arc::downgrade(&self.inner),
+        }
+    }
+}
+
+impl<T> Default for MutableVec<T> {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl<T> Clone for MutableVec<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Debug for MutableVec<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MutableVec")
+            .field("values", &self.inner.values)
+            .finish()
+    }
+}
+
+pub struct MutableVecReadGuard<'a, T> {
+    values: RwLockReadGuard<'a, Vec<T>>,
+}
+
+impl<'a, T> Deref for MutableVecReadGuard<'a, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.values
+    }
+}
+
+/// Allows modifying a [`MutableVec`] while recording the changes
+pub struct MutableVecWriteGuard<'a, T: Clone> {
+    /// Released before the changes are sent
+    values: Option<RwLockWriteGuard<'a, Vec<T>>>,
+    hooks: &'a HookList<DiffHook<VecDiff<T>>>,
+    diffs: Vec<VecDiff<T>>,
+}
+
+impl<'a, T: Clone> MutableVecWriteGuard<'a, T> {
+    fn values(&mut self) -> &mut Vec<T> {
+        self.values.as_mut().unwrap()
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.diffs.push(VecDiff::Push {
+            value: value.clone(),
+        });
+        self.values().push(value);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let values = self.values();
+        let value = values.pop()?;
+        let index = values.len();
+        self.diffs.push(VecDiff::Remove { index });
+        Some(value)
+    }
+
+    pub fn insert(&mut self, index: usize, value: T) {
+        self.values().insert(index, value.clone());
+        self.diffs.push(VecDiff::Insert { index, value });
+    }
+
+    pub fn remove(&mut self, index: usize) -> T {
+        let value = self.values().remove(index);
+        self.diffs.push(VecDiff::Remove { index });
+        value
+    }
+
+    /// Replaces the item at `index`
+    pub fn set(&mut self, index: usize, value: T) {
+        self.values()[index] = value.clone();
+        self.diffs.push(VecDiff::Update { index, value });
+    }
+
+    /// Removes the item at `from` and inserts it at `to`
+    pub fn move_item(&mut self, from: usize, to: usize) {
+        let value = self.values().remove(from);
+        self.values().insert(to, value);
+        self.diffs.push(VecDiff::Move { from, to });
+    }
+
+    pub fn replace(&mut self, values: Vec<T>) {
+        *self.values() = values.clone();
+        self.diffs.push(VecDiff::Replace { values });
+    }
+
+    pub fn clear(&mut self) {
+        self.values().clear();
+        self.diffs.push(VecDiff::Clear);
+    }
+}
+
+impl<'a, T: Clone> Deref for MutableVecWriteGuard<'a, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.values.as_ref().unwrap()
+    }
+}
+
+impl<'a, T: Clone> Drop for MutableVecWriteGuard<'a, T> {
+    fn drop(&mut self) {
+        // Release the lock first, as the signals may read the values as soon as they are woken
+        drop(self.values.take());
+
+        if self.diffs.is_empty() {
+            return;
+        }
+
+        // Changes before a reset are redundant
+        let start = self.diffs.iter().rposition(VecDiff::is_reset);
+        let diffs = &self.diffs[start.unwrap_or(0)..];
+
+        self.hooks
+            .for_each(|hook| hook.send(diffs.iter().cloned(), start.is_some()));
+    }
+}
+
+/// Yields batches of changes to a [`MutableVec`]
+pub struct MutableVecSignal<T> {
+    hook: Arc<DiffHook<VecDiff<T>>>,
+    state: Weak<MutableVecInner<T>>,
+}
+
+impl<'a, T> Signal<'a> for MutableVecSignal<T>
+where
+    T: 'a,
+{
+    type Item = Vec<VecDiff<T>>;
+
+    fn poll_changed(self: Pin<&'a mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match self.hook.poll_diffs(cx) {
+            Poll::Ready(diffs) => Poll::Ready(Some(diffs)),
+            Poll::Pending if self.state.strong_count() == 0 => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use futures::FutureExt;
+
+    use super::*;
+
+    #[test]
+    fn mutable_vec() {
+        let values = MutableVec::new(vec![1, 2, 3]);
+
+        let mut signal = values.signal();
+        let mut mirror = Vec::new();
+
+        let mut apply = |diffs: Vec<VecDiff<i32>>| {
+            diffs.into_iter().for_each(|v| v.apply(&mut mirror));
+            mirror.clone()
+        };
+
+        assert_eq!(
+            signal.next_value().now_or_never(),
+            Some(Some(vec![VecDiff::Replace {
+                values: vec![1, 2, 3]
+            }]))
+        );
+        assert_eq!(signal.next_value().now_or_never(), None);
+
+        {
+            let mut values = values.write();
+            values.push(4);
+            values.remove(0);
+            values.insert(1, 5);
+            values.move_item(0, 3);
+            values.set(0, 6);
+        }
+
+        assert_eq!(&*values.read(), [6, 3, 4, 2]);
+
+        let diffs = signal.next_value().now_or_never().unwrap().unwrap();
+        assert_eq!(
+            diffs,
+            [
+                VecDiff::Push { value: 4 },
+                VecDiff::Remove { index: 0 },
+                VecDiff::Insert { index: 1, value: 5 },
+                VecDiff::Move { from: 0, to: 3 },
+                VecDiff::Update { index: 0, value: 6 },
+            ]
+        );
+
+        apply(vec![VecDiff::Replace {
+            values: vec![1, 2, 3],
+        }]);
+        assert_eq!(apply(diffs), [6, 3, 4, 2]);
+
+        // No changes
+        drop(values.write());
+        assert_eq!(signal.next_value().now_or_never(), None);
+
+        drop(values);
+        assert_eq!(signal.next_value().now_or_never(), Some(None));
+    }
+
+    #[test]
+    fn merged_batches() {
+        let values = MutableVec::new(vec![1]);
+
+        let mut signal = values.signal();
+
+        values.write().push(2);
+        values.write().pop();
+        values.write().push(3);
+
+        assert_eq!(
+            signal.next_value().now_or_never(),
+            Some(Some(vec![
+                VecDiff::Replace { values: vec![1] },
+                VecDiff::Push { value: 2 },
+                VecDiff::Remove { index: 1 },
+                VecDiff::Push { value: 3 },
+            ]))
+        );
+
+        {
+            let mut values = values.write();
+            values.push(4);
+            values.clear();
+            values.push(5);
+        }
+
+        values.write().push(6);
+
+        // Changes before the clear are discarded
+        assert_eq!(
+            signal.next_value().now_or_never(),
+            Some(Some(vec![
+                VecDiff::Clear,
+                VecDiff::Push { value: 5 },
+                VecDiff::Push { value: 6 },
+            ]))
+        );
+
+        // Pending changes are still delivered after the vec is dropped
+        values.write().push(7);
+        drop(values);
+
+        assert_eq!(
+            signal.next_value().now_or_never(),
+            Some(Some(vec![VecDiff::Push { value: 7 }]))
+        );
+        assert_eq!(signal.next_value().now_or_never(), Some(None));
+    }
+
+    #[test]
+    fn wake_after_unlock() {
+        let values = MutableVec::new(vec![1]);
+        let mut signal = values.signal();
+        assert!(signal.next_value().now_or_never().is_some());
+
+        // A waker which reads the values right away must not deadlock
+        let unlocked = Arc::new(AtomicBool::new(false));
+        let waker = waker_fn::waker_fn({
+            let values = values.clone();
+            let unlocked = unlocked.clone();
+            move || {
+                let is_unlocked = values.inner.values.try_read().is_some();
+                unlocked.store(is_unlocked, Ordering::SeqCst);
+            }
+        });
+
+        assert!(signal
+            .next_value()
+            .poll_unpin(&mut Context::from_waker(&waker))
+            .is_pending());
+
+        values.write().push(2);
+        assert!(unlocked.load(Ordering::SeqCst));
+    }
+}
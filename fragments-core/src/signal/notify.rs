@@ -90,20 +90,19 @@ where
         if let Some(waiter) = waiter {
             if waiter.take_changed() {
                 let item = inner.value.lock().take();
-                eprintln!("Senders: {}", inner.senders.load(Ordering::Relaxed));
                 if let Some(item) = item {
                     Poll::Ready(Some(item))
                 } else if inner.senders.load(Ordering::Relaxed) == 0 {
-                    eprintln!("Disconnected");
+                    tracing::trace!("Channel disconnected");
                     Poll::Ready(None)
                 } else {
                     // Someone else took the value before us, store the waker into the queue once more
-                    eprintln!("No item available, storing waker");
+                    tracing::trace!("Value was taken by another receiver");
                     inner.waiters.lock().push(waiter.clone());
                     Poll::Pending
                 }
             } else if inner.senders.load(Ordering::Relaxed) == 0 {
-                eprintln!("Disconnected");
+                tracing::trace!("Channel disconnected");
                 Poll::Ready(None)
             } else {
                 // Store a waker
@@ -111,10 +110,10 @@ where
                 Poll::Pending
             }
         } else if let Some(item) = inner.value.lock().take() {
-            tracing::info!("An item was available immediately");
+            tracing::trace!("A value was available immediately");
             Poll::Ready(Some(item))
         } else if inner.senders.load(Ordering::Relaxed) == 0 {
-            tracing::info!("Disconnected");
+            tracing::trace!("Channel disconnected");
             Poll::Ready(None)
         } else {
             let waiter = Arc::new(Hook::new(false));
@@ -125,6 +124,11 @@ where
     }
 }
 
+/// Creates a channel which holds the latest value sent.
+///
+/// Each value is received by only one of the receivers, and a value which has not yet been
+/// received is overwritten by the next. Use [`broadcast_channel`](super::broadcast_channel) for
+/// every receiver to observe the values, or [`queue`](super::queue) to not drop any.
 pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
     let inner = Arc::new(Inner {
         value: Mutex::new(None),
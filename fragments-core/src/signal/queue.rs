@@ -0,0 +1,297 @@
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+
+use parking_lot::Mutex;
+use thiserror::Error;
+
+use super::{
+    waiter::{Hook, HookList},
+    Signal,
+};
+
+/// The value could not be sent as all receivers have been dropped
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("All receivers have been dropped")]
+pub struct SendError<T>(pub T);
+
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrySendError<T> {
+    #[error("The queue is full")]
+    Full(T),
+    #[error("All receivers have been dropped")]
+    Disconnected(T),
+}
+
+impl<T> TrySendError<T> {
+    /// Returns the value which failed to send
+    pub fn into_inner(self) -> T {
+        match self {
+            TrySendError::Full(v) | TrySendError::Disconnected(v) => v,
+        }
+    }
+}
+
+struct Inner<T> {
+    queue: Mutex<VecDeque<T>>,
+    capacity: usize,
+    senders: AtomicUsize,
+    receivers: AtomicUsize,
+    /// Woken when a value is sent
+    recv_hooks: HookList,
+    /// Woken when a value is received, making room in the queue
+    send_hooks: HookList,
+}
+
+/// Sends values into a bounded [`queue`]
+pub struct QueueSender<T> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T> QueueSender<T> {
+    /// Attempts to send a value without waiting for room in the queue
+    pub fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        if self.inner.receivers.load(Ordering::Relaxed) == 0 {
+            return Err(TrySendError::Disconnected(value));
+        }
+
+        {
+            let mut queue = self.inner.queue.lock();
+            if queue.len() >= self.inner.capacity {
+                return Err(TrySendError::Full(value));
+            }
+
+            queue.push_back(value);
+        }
+
+        self.inner.recv_hooks.wake_all();
+        Ok(())
+    }
+
+    /// Sends a value, waiting until there is room in the queue
+    pub fn send(&self, value: T) -> SendFuture<T> {
+        SendFuture {
+            sender: self,
+            value: Some(value),
+            hook: None,
+        }
+    }
+}
+
+impl<T> Drop for QueueSender<T> {
+    fn drop(&mut self) {
+        if self.inner.senders.fetch_sub(1, Ordering::Relaxed) == 1 {
+            self.inner.recv_hooks.wake_all();
+        }
+    }
+}
+
+impl<T> Clone for QueueSender<T> {
+    fn clone(&self) -> Self {
+        self.inner.senders.fetch_add(1, Ordering::Relaxed);
+
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for QueueSender<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("QueueSender").finish()
+    }
+}
+
+/// Future returned by [`QueueSender::send`]
+pub struct SendFuture<'a, T> {
+    sender: &'a QueueSender<T>,
+    value: Option<T>,
+    hook: Option<Arc<Hook>>,
+}
+
+impl<'a, T> Unpin for SendFuture<'a, T> {}
+
+impl<'a, T> Future for SendFuture<'a, T> {
+    type Output = Result<(), SendError<T>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let sender = self.sender;
+
+        // Store the waker before inspecting the queue so that no receive is missed in between
+        let hook = self.hook.get_or_insert_with(|| {
+            let hook = Arc::new(Hook::new(false));
+            sender.inner.send_hooks.push(Arc::downgrade(&hook));
+            hook
+        });
+
+        hook.set_waker(cx.waker().clone());
+        hook.take_changed();
+
+        let value = self
+            .value
+            .take()
+            .expect("SendFuture polled after completion");
+
+        match sender.try_send(value) {
+            Ok(()) => Poll::Ready(Ok(())),
+            Err(TrySendError::Disconnected(value)) => Poll::Ready(Err(SendError(value))),
+            Err(TrySendError::Full(value)) => {
+                self.value = Some(value);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Receives values from a bounded [`queue`].
+///
+/// Each value is received by exactly one receiver.
+pub struct QueueReceiver<T> {
+    hook: Arc<Hook>,
+    inner: Arc<Inner<T>>,
+}
+
+impl<T> QueueReceiver<T> {
+    fn new(inner: Arc<Inner<T>>) -> Self {
+        let hook = Arc::new(Hook::new(false));
+        inner.recv_hooks.push(Arc::downgrade(&hook));
+        inner.receivers.fetch_add(1, Ordering::Relaxed);
+
+        Self { hook, inner }
+    }
+
+    /// Returns the number of values currently in the queue
+    pub fn len(&self) -> usize {
+        self.inner.queue.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Drop for QueueReceiver<T> {
+    fn drop(&mut self) {
+        if self.inner.receivers.fetch_sub(1, Ordering::Relaxed) == 1 {
+            self.inner.send_hooks.wake_all();
+        }
+    }
+}
+
+impl<T> Clone for QueueReceiver<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.inner.clone())
+    }
+}
+
+impl<T> std::fmt::Debug for QueueReceiver<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("QueueReceiver").finish()
+    }
+}
+
+impl<'a, T> Signal<'a> for QueueReceiver<T>
+where
+    T: 'a,
+{
+    type Item = T;
+
+    fn poll_changed(self: Pin<&'a mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // Store the waker before inspecting the queue so that no send is missed in between
+        self.hook.set_waker(cx.waker().clone());
+        self.hook.take_changed();
+
+        let item = self.inner.queue.lock().pop_front();
+
+        if let Some(item) = item {
+            self.inner.send_hooks.wake_all();
+            Poll::Ready(Some(item))
+        } else if self.inner.senders.load(Ordering::Relaxed) == 0 {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// Creates a bounded multi-producer multi-consumer queue.
+///
+/// Unlike [`channel`](super::channel) no values are dropped; senders wait for room when the
+/// queue holds `capacity` values.
+///
+/// # Panics
+///
+/// If `capacity` is zero
+pub fn queue<T>(capacity: usize) -> (QueueSender<T>, QueueReceiver<T>) {
+    assert!(capacity > 0, "Queue capacity must be non-zero");
+
+    let inner = Arc::new(Inner {
+        queue: Mutex::new(VecDeque::with_capacity(capacity)),
+        capacity,
+        senders: AtomicUsize::new(1),
+        receivers: AtomicUsize::new(0),
+        recv_hooks: HookList::default(),
+        send_hooks: HookList::default(),
+    });
+
+    let receiver = QueueReceiver::new(inner.clone());
+
+    (QueueSender { inner }, receiver)
+}
+
+#[cfg(test)]
+mod test {
+    use futures::FutureExt;
+
+    use super::*;
+
+    #[test]
+    fn queue_bounded() {
+        let (tx, mut rx) = queue(2);
+        let mut rx2 = rx.clone();
+
+        assert_eq!(tx.try_send(1), Ok(()));
+        assert_eq!(tx.send(2).now_or_never(), Some(Ok(())));
+        assert_eq!(tx.try_send(3), Err(TrySendError::Full(3)));
+
+        let mut send = tx.send(3);
+        assert_eq!((&mut send).now_or_never(), None);
+
+        // Each value is received once
+        assert_eq!(rx.next_value().now_or_never(), Some(Some(1)));
+        assert_eq!(rx2.next_value().now_or_never(), Some(Some(2)));
+
+        assert_eq!(send.now_or_never(), Some(Ok(())));
+        assert_eq!(rx.next_value().now_or_never(), Some(Some(3)));
+        assert_eq!(rx2.next_value().now_or_never(), None);
+
+        drop((rx, rx2));
+        assert_eq!(tx.send(4).now_or_never(), Some(Err(SendError(4))));
+    }
+
+    #[tokio::test]
+    async fn queue_backpressure() {
+        let (tx, mut rx) = queue(1);
+
+        let producer = tokio::spawn(async move {
+            for i in 0..16 {
+                tx.send(i).await.unwrap();
+            }
+        });
+
+        let mut received = Vec::new();
+        while let Some(v) = rx.next_value().await {
+            received.push(v);
+        }
+
+        producer.await.unwrap();
+        assert_eq!(received, (0..16).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,394 @@
+use std::{
+    collections::BTreeMap,
+    io,
+    path::Path,
+    pin::Pin,
+    sync::{Arc, Weak},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use parking_lot::Mutex;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use super::{
+    waiter::{Hook, HookList},
+    Mutable, Signal,
+};
+
+#[derive(Error, Debug)]
+pub enum StoreError {
+    #[error("Failed to access the state file")]
+    Io(#[from] io::Error),
+    #[error("Failed to serialize or deserialize json")]
+    Json(#[from] serde_json::Error),
+    #[error("Failed to serialize ron")]
+    RonSerialize(#[from] ron::Error),
+    #[error("Failed to deserialize ron")]
+    RonDeserialize(#[from] ron::error::SpannedError),
+}
+
+/// The file format used by a [`StateStore`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Ron,
+}
+
+/// A value registered in the store
+trait StoreEntry: Send + Sync {
+    fn save(&self) -> Result<Value, StoreError>;
+    fn restore(&self, value: Value) -> Result<(), StoreError>;
+    /// Registers a hook which is woken when the value changes
+    fn subscribe(&self, hook: Weak<Hook>);
+}
+
+impl<T> StoreEntry for Mutable<T>
+where
+    T: Send + Sync + Serialize + DeserializeOwned,
+{
+    fn save(&self) -> Result<Value, StoreError> {
+        Ok(serde_json::to_value(&*self.read())?)
+    }
+
+    fn restore(&self, value: Value) -> Result<(), StoreError> {
+        self.set(serde_json::from_value(value)?);
+        Ok(())
+    }
+
+    fn subscribe(&self, hook: Weak<Hook>) {
+        self.push_waiter(hook)
+    }
+}
+
+#[derive(Default)]
+struct Entries {
+    values: BTreeMap<String, Box<dyn StoreEntry>>,
+    /// Loaded values which have not yet been registered
+    pending: BTreeMap<String, Value>,
+}
+
+#[derive(Default)]
+struct StoreInner {
+    entries: Mutex<Entries>,
+    /// Woken when any registered value changes
+    hooks: HookList,
+}
+
+/// Gives [`Mutable`] values an identity so that they can be persisted across restarts.
+///
+/// Each registered value is stored under a unique key, and the whole store is saved to and loaded
+/// from a single file.
+#[derive(Default, Clone)]
+pub struct StateStore {
+    inner: Arc<StoreInner>,
+}
+
+impl StateStore {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Registers a value under `key`.
+    ///
+    /// If a value for `key` has previously been loaded it is restored into the mutable. The store
+    /// keeps the mutable alive.
+    pub fn register<T>(&self, key: impl Into<String>, value: &Mutable<T>)
+    where
+        T: 'static + Send + Sync + Serialize + DeserializeOwned,
+    {
+        let key = key.into();
+        let mut entries = self.inner.entries.lock();
+
+        if let Some(saved) = entries.pending.remove(&key) {
+            if let Err(err) = value.restore(saved) {
+                tracing::warn!(%key, "Failed to restore value: {err}");
+            }
+        }
+
+        self.inner
+            .hooks
+            .for_each(|hook| value.subscribe(Arc::downgrade(hook)));
+
+        entries.values.insert(key, Box::new(value.clone()));
+    }
+
+    /// Returns the current value of every registered value
+    pub fn snapshot(&self) -> Result<BTreeMap<String, Value>, StoreError> {
+        let entries = self.inner.entries.lock();
+
+        entries
+            .values
+            .iter()
+            .map(|(key, entry)| Ok((key.clone(), entry.save()?)))
+            .collect()
+    }
+
+    /// Restores the values of a snapshot.
+    ///
+    /// Values for keys which are not yet registered are restored when registered.
+    pub fn restore(&self, snapshot: BTreeMap<String, Value>) -> Result<(), StoreError> {
+        let mut entries = self.inner.entries.lock();
+
+        for (key, value) in snapshot {
+            match entries.values.get(&key) {
+                Some(entry) => entry.restore(value)?,
+                None => {
+                    entries.pending.insert(key, value);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Serializes every registered value
+    pub fn to_string(&self, format: Format) -> Result<String, StoreError> {
+        let snapshot = self.snapshot()?;
+
+        match format {
+            Format::Json => Ok(serde_json::to_string_pretty(&snapshot)?),
+            Format::Ron => Ok(ron::ser::to_string_pretty(
+                &snapshot,
+                ron::ser::PrettyConfig::default(),
+            )?),
+        }
+    }
+
+    /// Restores the values from a string produced by [`Self::to_string`]
+    pub fn restore_str(&self, s: &str, format: Format) -> Result<(), StoreError> {
+        let snapshot = match format {
+            Format::Json => serde_json::from_str(s)?,
+            Format::Ron => ron::from_str(s)?,
+        };
+
+        self.restore(snapshot)
+    }
+
+    /// Writes every registered value to the file at `path`
+    pub fn save(&self, path: impl AsRef<Path>, format: Format) -> Result<(), StoreError> {
+        let path = path.as_ref();
+        let contents = self.to_string(format)?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Restores the values from the file at `path`.
+    ///
+    /// A missing file is not an error, as there is nothing to restore on the first startup.
+    pub fn load(&self, path: impl AsRef<Path>, format: Format) -> Result<(), StoreError> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => self.restore_str(&contents, format),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Returns a signal which fires each time any registered value changes
+    pub fn changes(&self) -> StoreSignal {
+        let hook = Arc::new(Hook::new(false));
+
+        self.inner.hooks.push(Arc::downgrade(&hook));
+        self.inner
+            .entries
+            .lock()
+            .values
+            .values()
+            .for_each(|entry| entry.subscribe(Arc::downgrade(&hook)));
+
+        StoreSignal {
+            hook,
+            store: Arc::downgrade(&self.inner),
+        }
+    }
+
+    /// Saves the store to `path` each time the values change, once no changes have been made for
+    /// `debounce`.
+    ///
+    /// Runs until dropped, or returns the first error.
+    pub async fn autosave(
+        &self,
+        path: impl AsRef<Path>,
+        format: Format,
+        debounce: Duration,
+    ) -> Result<(), StoreError> {
+        let mut changes = self.changes().debounce(debounce);
+
+        while changes.next_value().await.is_some() {
+            self.save(path.as_ref(), format)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for StateStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StateStore")
+            .field("keys", &self.inner.entries.lock().values.keys())
+            .finish()
+    }
+}
+
+/// Fires each time a value in a [`StateStore`] changes
+pub struct StoreSignal {
+    hook: Arc<Hook>,
+    store: Weak<StoreInner>,
+}
+
+impl<'a> Signal<'a> for StoreSignal {
+    type Item = ();
+
+    fn poll_changed(self: Pin<&'a mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.store.strong_count() == 0 {
+            return Poll::Ready(None);
+        }
+
+        self.hook.set_waker(cx.waker().clone());
+
+        if self.hook.take_changed() {
+            Poll::Ready(Some(()))
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use futures::FutureExt;
+    use serde::Deserialize;
+
+    use crate::time::{self, MockClock};
+
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Window {
+        width: u32,
+        height: u32,
+    }
+
+    #[test]
+    fn snapshot_restore() {
+        for format in [Format::Json, Format::Ron] {
+            let store = StateStore::new();
+
+            let window = Mutable::new(Window {
+                width: 800,
+                height: 600,
+            });
+            let dark_mode = Mutable::new(false);
+
+            store.register("window", &window);
+            store.register("dark_mode", &dark_mode);
+
+            window.write().width = 1024;
+            dark_mode.set(true);
+
+            let saved = store.to_string(format).unwrap();
+
+            // Restart
+            let store = StateStore::new();
+            store.restore_str(&saved, format).unwrap();
+
+            let window = Mutable::new(Window {
+                width: 800,
+                height: 600,
+            });
+            let dark_mode = Mutable::new(false);
+
+            store.register("window", &window);
+            store.register("dark_mode", &dark_mode);
+
+            assert_eq!(
+                window.get(),
+                Window {
+                    width: 1024,
+                    height: 600
+                }
+            );
+            assert!(dark_mode.get());
+        }
+    }
+
+    #[test]
+    fn changes() {
+        let store = StateStore::new();
+        let a = Mutable::new(1);
+        store.register("a", &a);
+
+        let mut changes = store.changes();
+        assert_eq!(changes.next_value().now_or_never(), None);
+
+        // Registered after the signal was created
+        let b = Mutable::new(2);
+        store.register("b", &b);
+
+        a.set(2);
+        assert_eq!(changes.next_value().now_or_never(), Some(Some(())));
+
+        b.set(3);
+        assert_eq!(changes.next_value().now_or_never(), Some(Some(())));
+
+        drop(store);
+        assert_eq!(changes.next_value().now_or_never(), Some(None));
+    }
+
+    fn load_value(path: &Path) -> i32 {
+        let restored = StateStore::new();
+        restored.load(path, Format::Json).unwrap();
+
+        let value = Mutable::new(0);
+        restored.register("value", &value);
+        value.get()
+    }
+
+    #[test]
+    fn autosave() {
+        let clock = MockClock::new();
+        let _guard = time::enter(clock.handle());
+
+        let path =
+            std::env::temp_dir().join(format!("fragments-state-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let store = StateStore::new();
+        let value = Mutable::new(1);
+        store.register("value", &value);
+
+        let mut task = Box::pin(store.autosave(&path, Format::Json, Duration::from_millis(50)));
+        assert!(task.as_mut().now_or_never().is_none());
+
+        value.set(2);
+        value.set(3);
+        assert!(task.as_mut().now_or_never().is_none());
+
+        // Nothing is written until the changes have settled
+        clock.advance(Duration::from_millis(49));
+        assert!(task.as_mut().now_or_never().is_none());
+        assert!(!path.exists());
+
+        clock.advance(Duration::from_millis(1));
+        assert!(task.as_mut().now_or_never().is_none());
+        assert_eq!(load_value(&path), 3);
+
+        value.set(4);
+        assert!(task.as_mut().now_or_never().is_none());
+        assert_eq!(load_value(&path), 3);
+
+        clock.advance(Duration::from_millis(50));
+        assert!(task.as_mut().now_or_never().is_none());
+        assert_eq!(load_value(&path), 4);
+
+        drop(task);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,132 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use pin_project::pin_project;
+
+use super::Signal;
+
+/// Yields the values of the most recent inner signal, switching to a new inner signal whenever the
+/// outer signal changes.
+#[pin_project]
+pub struct Switch<S, I> {
+    #[pin]
+    signal: S,
+    #[pin]
+    inner: Option<I>,
+    inner_closed: bool,
+    is_closed: bool,
+}
+
+impl<S, I> Switch<S, I> {
+    pub(crate) fn new(signal: S) -> Self {
+        Self {
+            signal,
+            inner: None,
+            inner_closed: false,
+            is_closed: false,
+        }
+    }
+}
+
+impl<'a, S, I> Signal<'a> for Switch<S, I>
+where
+    S: for<'x> Signal<'x, Item = I>,
+    I: Signal<'a>,
+{
+    type Item = I::Item;
+
+    fn poll_changed(self: Pin<&'a mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut p = self.project();
+
+        // Switch to the most recent inner signal
+        while !*p.is_closed {
+            match p.signal.as_mut().poll_changed(cx) {
+                Poll::Ready(Some(inner)) => {
+                    p.inner.set(Some(inner));
+                    *p.inner_closed = false;
+                }
+                Poll::Ready(None) => *p.is_closed = true,
+                Poll::Pending => break,
+            }
+        }
+
+        match p.inner.as_pin_mut() {
+            Some(inner) if !*p.inner_closed => match inner.poll_changed(cx) {
+                Poll::Ready(Some(v)) => Poll::Ready(Some(v)),
+                Poll::Ready(None) => {
+                    // Wait for the next inner signal
+                    *p.inner_closed = true;
+                    if *p.is_closed {
+                        Poll::Ready(None)
+                    } else {
+                        Poll::Pending
+                    }
+                }
+                Poll::Pending => Poll::Pending,
+            },
+            _ if *p.is_closed => Poll::Ready(None),
+            _ => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use futures::FutureExt;
+
+    use crate::signal::Mutable;
+
+    use super::*;
+
+    #[test]
+    fn switch_map() {
+        let use_a = Mutable::new(true);
+        let a = Mutable::new(1);
+        let b = Mutable::new(2);
+
+        let mut s = use_a.signal().switch_map({
+            let a = a.clone();
+            let b = b.clone();
+            move |use_a| if use_a { a.signal() } else { b.signal() }
+        });
+
+        assert_eq!(s.next_value().now_or_never(), Some(Some(1)));
+        assert_eq!(s.next_value().now_or_never(), None);
+
+        *a.write() = 3;
+        assert_eq!(s.next_value().now_or_never(), Some(Some(3)));
+
+        *use_a.write() = false;
+        assert_eq!(s.next_value().now_or_never(), Some(Some(2)));
+
+        // No longer observed
+        *a.write() = 4;
+        assert_eq!(s.next_value().now_or_never(), None);
+
+        *b.write() = 5;
+        assert_eq!(s.next_value().now_or_never(), Some(Some(5)));
+
+        // The inner signal is kept until it closes
+        drop(use_a);
+        *b.write() = 6;
+        assert_eq!(s.next_value().now_or_never(), Some(Some(6)));
+    }
+
+    #[test]
+    fn switch_inner_closed() {
+        let outer = Mutable::new(Mutable::new(1));
+
+        let mut s = outer.signal_ref().map(|v| v.signal()).switch();
+
+        assert_eq!(s.next_value().now_or_never(), Some(Some(1)));
+
+        // Closes the inner signal, but the outer signal remains
+        *outer.write() = Mutable::new(2);
+        assert_eq!(s.next_value().now_or_never(), Some(Some(2)));
+
+        drop(outer);
+        assert_eq!(s.next_value().now_or_never(), Some(None));
+    }
+}
@@ -0,0 +1,462 @@
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use futures::Future;
+use pin_project::pin_project;
+
+use crate::time::{current, Interval, MissedTickBehavior, Sleep, TimersHandle};
+
+use super::Signal;
+
+/// Yields a value once no new value has arrived for `duration`.
+///
+/// See [`Signal::debounce`]
+#[pin_project]
+pub struct Debounce<S, T> {
+    #[pin]
+    signal: S,
+    #[pin]
+    sleep: Sleep,
+    timers: TimersHandle,
+    duration: Duration,
+    pending: Option<T>,
+    is_closed: bool,
+}
+
+impl<S, T> Debounce<S, T> {
+    pub(crate) fn new(signal: S, duration: Duration) -> Self {
+        let timers = current();
+
+        Self {
+            signal,
+            sleep: timers.sleep_until(timers.now()),
+            timers,
+            duration,
+            pending: None,
+            is_closed: false,
+        }
+    }
+}
+
+impl<'a, S, T> Signal<'a> for Debounce<S, T>
+where
+    S: for<'x> Signal<'x, Item = T>,
+    T: 'a,
+{
+    type Item = T;
+
+    fn poll_changed(self: Pin<&'a mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut p = self.project();
+
+        if *p.is_closed {
+            return Poll::Ready(None);
+        }
+
+        loop {
+            match p.signal.as_mut().poll_changed(cx) {
+                Poll::Ready(Some(v)) => {
+                    *p.pending = Some(v);
+                    p.sleep.as_mut().reset(p.timers.now() + *p.duration);
+                }
+                Poll::Ready(None) => {
+                    // Flush the last value
+                    *p.is_closed = true;
+                    return Poll::Ready(p.pending.take());
+                }
+                Poll::Pending => break,
+            }
+        }
+
+        if p.pending.is_some() && p.sleep.poll(cx).is_ready() {
+            return Poll::Ready(p.pending.take());
+        }
+
+        Poll::Pending
+    }
+}
+
+/// Yields at most one value per `duration`.
+///
+/// See [`Signal::throttle`]
+#[pin_project]
+pub struct Throttle<S, T> {
+    #[pin]
+    signal: S,
+    /// Fires at the end of the current window
+    #[pin]
+    sleep: Sleep,
+    timers: TimersHandle,
+    duration: Duration,
+    is_throttled: bool,
+    pending: Option<T>,
+    is_closed: bool,
+}
+
+impl<S, T> Throttle<S, T> {
+    pub(crate) fn new(signal: S, duration: Duration) -> Self {
+        let timers = current();
+
+        Self {
+            signal,
+            sleep: timers.sleep_until(timers.now()),
+            timers,
+            duration,
+            is_throttled: false,
+            pending: None,
+            is_closed: false,
+        }
+    }
+}
+
+impl<'a, S, T> Signal<'a> for Throttle<S, T>
+where
+    S: for<'x> Signal<'x, Item = T>,
+    T: 'a,
+{
+    type Item = T;
+
+    fn poll_changed(self: Pin<&'a mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut p = self.project();
+
+        if *p.is_closed {
+            return Poll::Ready(None);
+        }
+
+        loop {
+            match p.signal.as_mut().poll_changed(cx) {
+                Poll::Ready(Some(v)) if *p.is_throttled => *p.pending = Some(v),
+                Poll::Ready(Some(v)) => {
+                    *p.is_throttled = true;
+                    p.sleep.as_mut().reset(p.timers.now() + *p.duration);
+                    return Poll::Ready(Some(v));
+                }
+                Poll::Ready(None) => {
+                    *p.is_closed = true;
+                    return Poll::Ready(p.pending.take());
+                }
+                Poll::Pending => break,
+            }
+        }
+
+        if *p.is_throttled && p.sleep.as_mut().poll(cx).is_ready() {
+            // Yield the latest value received during the window, and start a new window
+            if let Some(v) = p.pending.take() {
+                p.sleep.as_mut().reset(p.timers.now() + *p.duration);
+                return Poll::Ready(Some(v));
+            }
+
+            *p.is_throttled = false;
+        }
+
+        Poll::Pending
+    }
+}
+
+/// Yields each value `duration` after it was received.
+///
+/// See [`Signal::delay`]
+#[pin_project]
+pub struct Delay<S, T> {
+    #[pin]
+    signal: S,
+    /// Armed to the deadline of the first queued value
+    #[pin]
+    sleep: Sleep,
+    timers: TimersHandle,
+    is_armed: bool,
+    duration: Duration,
+    queue: VecDeque<(Instant, T)>,
+    is_closed: bool,
+}
+
+impl<S, T> Delay<S, T> {
+    pub(crate) fn new(signal: S, duration: Duration) -> Self {
+        let timers = current();
+
+        Self {
+            signal,
+            sleep: timers.sleep_until(timers.now()),
+            timers,
+            is_armed: false,
+            duration,
+            queue: VecDeque::new(),
+            is_closed: false,
+        }
+    }
+}
+
+impl<'a, S, T> Signal<'a> for Delay<S, T>
+where
+    S: for<'x> Signal<'x, Item = T>,
+    T: 'a,
+{
+    type Item = T;
+
+    fn poll_changed(self: Pin<&'a mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut p = self.project();
+
+        while !*p.is_closed {
+            match p.signal.as_mut().poll_changed(cx) {
+                Poll::Ready(Some(v)) => p.queue.push_back((p.timers.now() + *p.duration, v)),
+                Poll::Ready(None) => *p.is_closed = true,
+                Poll::Pending => break,
+            }
+        }
+
+        if let Some(&(deadline, _)) = p.queue.front() {
+            if !*p.is_armed || p.sleep.deadline() != deadline {
+                p.sleep.as_mut().reset(deadline);
+                *p.is_armed = true;
+            }
+
+            if p.sleep.as_mut().poll(cx).is_ready() {
+                *p.is_armed = false;
+                return Poll::Ready(p.queue.pop_front().map(|(_, v)| v));
+            }
+        } else if *p.is_closed {
+            return Poll::Ready(None);
+        }
+
+        Poll::Pending
+    }
+}
+
+/// Yields the most recent value at a fixed interval.
+///
+/// Ticks without a new value are skipped, so a value received after an idle period is yielded
+/// right away, and the following values are sampled at the original schedule.
+///
+/// See [`Signal::sample`]
+#[pin_project]
+pub struct Sample<S, T> {
+    #[pin]
+    signal: S,
+    interval: Interval,
+    pending: Option<T>,
+    is_closed: bool,
+}
+
+impl<S, T> Sample<S, T> {
+    pub(crate) fn new(signal: S, period: Duration) -> Self {
+        Self {
+            signal,
+            interval: current()
+                .interval(period)
+                .with_missed_tick_behavior(MissedTickBehavior::Skip),
+            pending: None,
+            is_closed: false,
+        }
+    }
+}
+
+impl<'a, S, T> Signal<'a> for Sample<S, T>
+where
+    S: for<'x> Signal<'x, Item = T>,
+    T: 'a,
+{
+    type Item = T;
+
+    fn poll_changed(self: Pin<&'a mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut p = self.project();
+
+        while !*p.is_closed {
+            match p.signal.as_mut().poll_changed(cx) {
+                Poll::Ready(Some(v)) => *p.pending = Some(v),
+                Poll::Ready(None) => *p.is_closed = true,
+                Poll::Pending => break,
+            }
+        }
+
+        if p.pending.is_none() {
+            return if *p.is_closed {
+                Poll::Ready(None)
+            } else {
+                Poll::Pending
+            };
+        }
+
+        // The next tick is registered when polled again after yielding
+        if p.interval.poll_tick(cx).is_ready() {
+            return Poll::Ready(p.pending.take());
+        }
+
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use futures::FutureExt;
+
+    use crate::{
+        signal::Mutable,
+        time::{enter, MockClock},
+    };
+
+    use super::*;
+
+    #[test]
+    fn debounce() {
+        let clock = MockClock::new();
+        let _guard = enter(clock.handle());
+
+        let value = Mutable::new(0);
+        let mut signal = value.signal().debounce(Duration::from_millis(100));
+        assert_eq!(signal.next_value().now_or_never(), None);
+
+        clock.advance(Duration::from_millis(50));
+        value.set(1);
+        assert_eq!(signal.next_value().now_or_never(), None);
+
+        clock.advance(Duration::from_millis(99));
+        assert_eq!(signal.next_value().now_or_never(), None);
+
+        clock.advance(Duration::from_millis(1));
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(1)));
+
+        // Each value restarts the wait
+        for i in 2..=5 {
+            clock.advance(Duration::from_millis(50));
+            value.set(i);
+            assert_eq!(signal.next_value().now_or_never(), None);
+        }
+
+        clock.advance(Duration::from_millis(100));
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(5)));
+
+        value.set(6);
+        assert_eq!(signal.next_value().now_or_never(), None);
+        drop(value);
+
+        // The last value is flushed when the signal is closed
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(6)));
+        assert_eq!(signal.next_value().now_or_never(), Some(None));
+    }
+
+    #[test]
+    fn throttle() {
+        let clock = MockClock::new();
+        let _guard = enter(clock.handle());
+
+        let value = Mutable::new(0);
+        let mut signal = value.signal().throttle(Duration::from_millis(100));
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(0)));
+
+        value.set(1);
+        value.set(2);
+        assert_eq!(signal.next_value().now_or_never(), None);
+
+        clock.advance(Duration::from_millis(99));
+        assert_eq!(signal.next_value().now_or_never(), None);
+
+        // The latest value is yielded at the end of the window
+        clock.advance(Duration::from_millis(1));
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(2)));
+
+        // The window ends without any value
+        clock.advance(Duration::from_millis(150));
+        assert_eq!(signal.next_value().now_or_never(), None);
+
+        value.set(3);
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(3)));
+
+        value.set(4);
+        assert_eq!(signal.next_value().now_or_never(), None);
+        drop(value);
+
+        // The pending value is flushed when the signal is closed
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(4)));
+        assert_eq!(signal.next_value().now_or_never(), Some(None));
+    }
+
+    #[test]
+    fn delay() {
+        let clock = MockClock::new();
+        let _guard = enter(clock.handle());
+
+        let value = Mutable::new(1);
+        let mut signal = value.signal().delay(Duration::from_millis(100));
+        assert_eq!(signal.next_value().now_or_never(), None);
+
+        clock.advance(Duration::from_millis(50));
+        value.set(2);
+        assert_eq!(signal.next_value().now_or_never(), None);
+
+        clock.advance(Duration::from_millis(49));
+        assert_eq!(signal.next_value().now_or_never(), None);
+
+        clock.advance(Duration::from_millis(1));
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(1)));
+        assert_eq!(signal.next_value().now_or_never(), None);
+
+        // Each value keeps its own delay
+        clock.advance(Duration::from_millis(50));
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(2)));
+
+        drop(value);
+        assert_eq!(signal.next_value().now_or_never(), Some(None));
+    }
+
+    #[test]
+    fn sample() {
+        let clock = MockClock::new();
+        let _guard = enter(clock.handle());
+
+        let value = Mutable::new(0);
+        let mut signal = value.signal().sample(Duration::from_millis(100));
+
+        // The first tick is immediate
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(0)));
+
+        value.set(1);
+        value.set(2);
+        assert_eq!(signal.next_value().now_or_never(), None);
+
+        clock.advance(Duration::from_millis(99));
+        assert_eq!(signal.next_value().now_or_never(), None);
+
+        clock.advance(Duration::from_millis(1));
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(2)));
+
+        // Nothing changed during the next period, so the value is yielded at the missed tick
+        clock.advance(Duration::from_millis(150));
+        assert_eq!(signal.next_value().now_or_never(), None);
+
+        value.set(3);
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(3)));
+
+        value.set(4);
+        assert_eq!(signal.next_value().now_or_never(), None);
+
+        clock.advance(Duration::from_millis(50));
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(4)));
+
+        // Several missed ticks are skipped rather than fired in a burst
+        clock.advance(Duration::from_millis(250));
+        value.set(5);
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(5)));
+
+        value.set(6);
+        assert_eq!(signal.next_value().now_or_never(), None);
+
+        clock.advance(Duration::from_millis(49));
+        assert_eq!(signal.next_value().now_or_never(), None);
+
+        clock.advance(Duration::from_millis(1));
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(6)));
+
+        value.set(7);
+        assert_eq!(signal.next_value().now_or_never(), None);
+        drop(value);
+
+        // The last value is still sampled after the signal is closed
+        assert_eq!(signal.next_value().now_or_never(), None);
+        clock.advance(Duration::from_millis(100));
+        assert_eq!(signal.next_value().now_or_never(), Some(Some(7)));
+        assert_eq!(signal.next_value().now_or_never(), Some(None));
+    }
+}
@@ -1,14 +1,17 @@
 use std::{
+    mem,
     pin::Pin,
     sync::{
         atomic::{AtomicBool, Ordering},
-        Weak,
+        Arc, Weak,
     },
     task::{Context, Poll, Waker as AsyncWaker},
 };
 
 use parking_lot::Mutex;
 
+use super::batch::defer_wake;
+
 /// Abstraction over the most recent async waker and changed flag
 pub(crate) struct Hook {
     changed: AtomicBool,
@@ -36,32 +39,52 @@ impl Hook {
         *self.waker.lock() = Some(waker);
     }
 
-    pub fn wake(&self) {
+    /// Marks the hook as changed and wakes the observer.
+    ///
+    /// The wake is deferred until the end of the current [`batch`](super::batch), if any.
+    pub fn wake(self: &Arc<Self>) {
+        if !defer_wake(self) {
+            self.wake_now()
+        }
+    }
+
+    pub(crate) fn mark_changed(&self) {
         self.changed.store(true, Ordering::SeqCst);
+    }
+
+    pub(crate) fn wake_now(&self) {
+        self.mark_changed();
         if let Some(waker) = &*self.waker.lock() {
             waker.wake_by_ref()
         }
     }
 }
 
-#[derive(Default)]
-pub(crate) struct HookList {
-    inner: Mutex<Vec<Weak<Hook>>>,
+pub(crate) struct HookList<H = Hook> {
+    inner: Mutex<Vec<Weak<H>>>,
 }
 
-impl HookList {
-    pub fn push(&self, value: Weak<Hook>) {
+impl<H> Default for HookList<H> {
+    fn default() -> Self {
+        Self {
+            inner: Default::default(),
+        }
+    }
+}
+
+impl<H> HookList<H> {
+    pub fn push(&self, value: Weak<H>) {
         let mut inner = self.inner.lock();
 
         inner.push(value);
         inner.retain(|v| v.strong_count() > 0);
     }
 
-    /// Invokes all hooks
-    pub fn wake_all(&self) {
+    /// Invokes `f` for every hook which is still alive
+    pub fn for_each(&self, mut f: impl FnMut(&Arc<H>)) {
         self.inner.lock().retain(|v| {
             if let Some(w) = v.upgrade() {
-                w.wake();
+                f(&w);
                 true
             } else {
                 false
@@ -69,3 +92,61 @@ impl HookList {
         })
     }
 }
+
+impl HookList {
+    /// Invokes all hooks
+    pub fn wake_all(&self) {
+        self.for_each(Hook::wake)
+    }
+}
+
+/// A hook which accumulates the changes since the observer last took them.
+///
+/// This allows a slow observer to receive all changes as a single batch rather than missing some.
+pub(crate) struct DiffHook<D> {
+    hook: Arc<Hook>,
+    diffs: Mutex<Vec<D>>,
+}
+
+impl<D> DiffHook<D> {
+    pub fn new(initial: Vec<D>) -> Self {
+        Self {
+            hook: Arc::new(Hook::new(!initial.is_empty())),
+            diffs: Mutex::new(initial),
+        }
+    }
+
+    /// Appends the changes and wakes the observer.
+    ///
+    /// If `reset` is set, the changes supersede all pending changes.
+    pub fn send(&self, diffs: impl IntoIterator<Item = D>, reset: bool) {
+        {
+            let mut pending = self.diffs.lock();
+            if reset {
+                pending.clear();
+            }
+
+            pending.extend(diffs);
+        }
+
+        self.hook.wake();
+    }
+
+    pub fn wake(&self) {
+        self.hook.wake()
+    }
+
+    /// Takes all pending changes, or stores the waker if there are none
+    pub fn poll_diffs(&self, cx: &mut Context<'_>) -> Poll<Vec<D>> {
+        self.hook.set_waker(cx.waker().clone());
+
+        if self.hook.take_changed() {
+            let diffs = mem::take(&mut *self.diffs.lock());
+            if !diffs.is_empty() {
+                return Poll::Ready(diffs);
+            }
+        }
+
+        Poll::Pending
+    }
+}
@@ -0,0 +1,297 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use flax::{entity_ids, name, Component, ComponentValue, Entity, Query, World};
+
+use crate::{
+    components::ordered_children,
+    effect::Executor,
+    events::EventRegistry,
+    frame::Frame,
+    time::{self, MockClock, Sleep, TimersHandle},
+    Widget,
+};
+
+/// The maximum number of executor updates before the UI is considered to never settle
+const MAX_UPDATES: usize = 1024;
+
+/// Deterministic single threaded harness for testing widgets.
+///
+/// Owns the [`Frame`], [`Executor`] and [`EventRegistry`] which are otherwise set up by the
+/// backend. All effects use the timers of a [`MockClock`], which only advances through
+/// [`TestApp::advance`].
+pub struct TestApp {
+    frame: Frame,
+    executor: Executor<Frame>,
+    events: Arc<EventRegistry>,
+    clock: MockClock,
+}
+
+impl TestApp {
+    pub fn new() -> Self {
+        let clock = MockClock::new();
+
+        let mut executor = Executor::new();
+        executor.set_timers(clock.handle());
+
+        let events = Arc::new(EventRegistry::new());
+        let frame = Frame::new(World::new(), executor.spawner(), events.clone());
+
+        Self {
+            frame,
+            executor,
+            events,
+            clock,
+        }
+    }
+
+    /// Mounts a widget at the root and runs until the UI has settled.
+    ///
+    /// Returns the entity of the widget.
+    pub fn mount(&mut self, widget: impl Widget) -> Entity {
+        let _guard = time::enter(self.clock.handle());
+        let id = self.frame.spawn_root(widget);
+        self.run_until_stalled();
+        id
+    }
+
+    /// Updates the executor until no more work is left.
+    ///
+    /// The UI is settled once no task is ready to be polled and no timer is due, after notifying
+    /// the observers of the world of any changes.
+    ///
+    /// Returns the number of updates.
+    ///
+    /// # Panics
+    /// If the UI does not settle, which usually means that tasks keep waking each other.
+    pub fn run_until_stalled(&mut self) -> usize {
+        for updates in 0..MAX_UPDATES {
+            self.frame.observe_changes();
+
+            // Fire timers which expired without the clock advancing
+            if self
+                .clock
+                .next_deadline()
+                .is_some_and(|deadline| deadline <= self.clock.now())
+            {
+                self.clock.advance(Duration::ZERO);
+            }
+
+            if self.executor.is_idle() {
+                return updates;
+            }
+
+            self.executor.update(&mut self.frame);
+        }
+
+        panic!("The UI did not settle after {MAX_UPDATES} updates")
+    }
+
+    /// Advances the virtual clock, firing any expired timers, and runs until the UI has settled.
+    pub fn advance(&mut self, duration: Duration) {
+        self.clock.advance(duration);
+        self.run_until_stalled();
+    }
+
+    /// Returns the current time of the virtual clock
+    pub fn now(&self) -> Instant {
+        self.clock.now()
+    }
+
+    pub fn clock(&self) -> &MockClock {
+        &self.clock
+    }
+
+    /// Returns a handle to the timers driven by the virtual clock
+    pub fn timers(&self) -> TimersHandle {
+        self.clock.handle()
+    }
+
+    /// Returns a future which completes after `duration` has passed on the virtual clock
+    pub fn sleep(&self, duration: Duration) -> Sleep {
+        self.clock.handle().sleep(duration)
+    }
+
+    /// Emits a global event to all listeners and runs until the UI has settled
+    pub fn emit<T: 'static>(&mut self, event: T) {
+        let _guard = time::enter(self.clock.handle());
+        self.events.emit(&mut self.frame, &event);
+        self.run_until_stalled();
+    }
+
+    /// Returns all entities with the given name.
+    ///
+    /// Widgets are named after their type when attached.
+    pub fn find_by_name(&self, widget_name: &str) -> Vec<Entity> {
+        Query::new((entity_ids(), name()))
+            .borrow(&self.frame.world)
+            .iter()
+            .filter(|(_, name)| *name == widget_name)
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    /// Returns all entities which have `component`
+    pub fn find_with<T: ComponentValue>(&self, component: Component<T>) -> Vec<Entity> {
+        Query::new(entity_ids())
+            .with(component)
+            .borrow(&self.frame.world)
+            .iter()
+            .collect()
+    }
+
+    /// Returns the value of `component` for the entity
+    pub fn get<T: ComponentValue + Clone>(&self, id: Entity, component: Component<T>) -> Option<T> {
+        self.frame.world.get(id, component).ok().map(|v| v.clone())
+    }
+
+    /// Returns the children of the widget in order
+    pub fn children(&self, id: Entity) -> Vec<Entity> {
+        self.get(id, ordered_children()).unwrap_or_default()
+    }
+
+    pub fn world(&self) -> &World {
+        &self.frame.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.frame.world
+    }
+
+    pub fn frame_mut(&mut self) -> &mut Frame {
+        &mut self.frame
+    }
+
+    pub fn executor_mut(&mut self) -> &mut Executor<Frame> {
+        &mut self.executor
+    }
+}
+
+impl Default for TestApp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for TestApp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TestApp")
+            .field("now", &self.clock.now())
+            .finish()
+    }
+}
+
+/// Widgets and helpers shared by the widget tests
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use flax::Entity;
+
+    use crate::{components::text, Scope, Widget};
+
+    use super::TestApp;
+
+    /// Displays a fixed text
+    pub(crate) struct Label(pub &'static str);
+
+    impl Widget for Label {
+        fn mount(self, scope: &mut Scope<'_>) {
+            scope.set(text(), self.0.into());
+        }
+    }
+
+    /// Returns the text of each child of the widget in order
+    pub(crate) fn labels(app: &TestApp, id: Entity) -> Vec<String> {
+        app.children(id)
+            .into_iter()
+            .map(|child| app.get(child, text()).unwrap())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::task::Poll;
+
+    use palette::Srgba;
+
+    use crate::{
+        components::{color, text},
+        Scope,
+    };
+
+    use super::*;
+
+    struct Increment;
+
+    struct Counter;
+
+    impl Widget for Counter {
+        fn mount(self, scope: &mut Scope<'_>) {
+            scope.set(text(), "0".into());
+
+            let mut count = 0;
+            scope.on_global_event(move |scope, &Increment| {
+                count += 1;
+                scope.set(text(), count.to_string());
+            });
+
+            scope.use_async(time::sleep(Duration::from_secs(1)), |scope, _| {
+                scope.set(color(), Srgba::new(1.0, 0.0, 0.0, 1.0))
+            });
+        }
+    }
+
+    #[test]
+    fn test_app() {
+        let mut app = TestApp::new();
+        let id = app.mount(Counter);
+
+        assert_eq!(app.find_with(text()), [id]);
+        assert_eq!(app.get(id, text()).as_deref(), Some("0"));
+
+        app.emit(Increment);
+        app.emit(Increment);
+        assert_eq!(app.get(id, text()).as_deref(), Some("2"));
+
+        app.advance(Duration::from_millis(999));
+        assert_eq!(app.get(id, color()), None);
+
+        app.advance(Duration::from_millis(1));
+        assert_eq!(app.get(id, color()), Some(Srgba::new(1.0, 0.0, 0.0, 1.0)));
+
+        // Settled
+        assert_eq!(app.run_until_stalled(), 0);
+    }
+
+    struct Yielding(usize);
+
+    impl Widget for Yielding {
+        fn mount(self, scope: &mut Scope<'_>) {
+            let mut remaining = self.0;
+
+            let work = futures::future::poll_fn(move |cx| {
+                if remaining == 0 {
+                    return Poll::Ready(());
+                }
+
+                // Continue the work next update
+                remaining -= 1;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            });
+
+            scope.use_async(work, |scope, _| scope.set(text(), "done".into()));
+        }
+    }
+
+    #[test]
+    fn yielding_task() {
+        let mut app = TestApp::new();
+        let id = app.mount(Yielding(10));
+
+        // A task waking itself to continue is not considered stalled
+        assert_eq!(app.get(id, text()).as_deref(), Some("done"));
+    }
+}
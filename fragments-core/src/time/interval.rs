@@ -7,14 +7,26 @@ use std::{
 use futures::{ready, Future, Stream};
 use pin_project::pin_project;
 
-use crate::time::{Sleep, TimersHandle, GLOBAL_TIMER};
+use crate::time::{current, Sleep, TimersHandle};
 
 pub fn interval(period: Duration) -> Interval {
-    Interval::new(&GLOBAL_TIMER, Instant::now(), period)
+    current().interval(period)
 }
 
 pub fn interval_at(start: Instant, period: Duration) -> Interval {
-    Interval::new(&GLOBAL_TIMER, start, period)
+    current().interval_at(start, period)
+}
+
+/// Determines how an [`Interval`] catches up after missing ticks, such as after a stall
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MissedTickBehavior {
+    /// Fire the missed ticks as fast as possible until caught up with the original schedule
+    #[default]
+    Burst,
+    /// Fire once, and schedule the following ticks one period after the missed tick fired
+    Delay,
+    /// Fire once, and skip the remaining missed ticks to keep the original schedule
+    Skip,
 }
 
 /// Ticks at a fixed interval.
@@ -22,7 +34,9 @@ pub fn interval_at(start: Instant, period: Duration) -> Interval {
 #[derive(Debug)]
 pub struct Interval {
     sleep: Pin<Box<Sleep>>,
+    timers: TimersHandle,
     period: Duration,
+    missed_tick_behavior: MissedTickBehavior,
 }
 
 impl Interval {
@@ -30,10 +44,25 @@ impl Interval {
     pub fn new(handle: &TimersHandle, start: Instant, period: Duration) -> Self {
         Self {
             sleep: Box::pin(Sleep::new(handle, start)),
+            timers: handle.clone(),
             period,
+            missed_tick_behavior: MissedTickBehavior::default(),
         }
     }
 
+    pub fn missed_tick_behavior(&self) -> MissedTickBehavior {
+        self.missed_tick_behavior
+    }
+
+    pub fn set_missed_tick_behavior(&mut self, behavior: MissedTickBehavior) {
+        self.missed_tick_behavior = behavior;
+    }
+
+    pub fn with_missed_tick_behavior(mut self, behavior: MissedTickBehavior) -> Self {
+        self.missed_tick_behavior = behavior;
+        self
+    }
+
     pub async fn tick(&mut self) -> Instant {
         futures::future::poll_fn(move |cx| self.poll_tick(cx)).await
     }
@@ -45,7 +74,15 @@ impl Interval {
         ready!(self.sleep.as_mut().poll(cx));
 
         // Calculate the next deadline
-        let new_deadline = deadline + self.period;
+        let now = self.timers.now();
+        let new_deadline = match self.missed_tick_behavior {
+            MissedTickBehavior::Delay if now > deadline + self.period => now + self.period,
+            MissedTickBehavior::Skip if now >= deadline + self.period && !self.period.is_zero() => {
+                let missed = (now - deadline).as_nanos() / self.period.as_nanos();
+                deadline + self.period * (missed.min(u32::MAX as u128 - 1) as u32 + 1)
+            }
+            _ => deadline + self.period,
+        };
 
         // Reset the timer
         // Note: will not be registered until the interval is polled again
@@ -67,9 +104,9 @@ impl Stream for Interval {
 mod test {
     use std::thread;
 
-    use futures::StreamExt;
+    use futures::{FutureExt, StreamExt};
 
-    use crate::time::{assert_dur, Timers};
+    use crate::time::{assert_dur, MockClock, Timers};
 
     use super::*;
 
@@ -130,6 +167,42 @@ mod test {
         assert_interval(now, interval, expected);
     }
 
+    fn assert_ticks(interval: &mut Interval, expected: &[Instant]) {
+        let ticks = std::iter::from_fn(|| interval.next().now_or_never().flatten());
+        assert_eq!(ticks.collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn missed_ticks() {
+        let period = Duration::from_millis(100);
+        let ms = Duration::from_millis;
+
+        for (behavior, missed, next) in [
+            (MissedTickBehavior::Burst, vec![0, 100, 200, 300], 400),
+            (MissedTickBehavior::Delay, vec![0, 100], 450),
+            (MissedTickBehavior::Skip, vec![0, 100], 400),
+        ] {
+            let clock = MockClock::new();
+            let start = clock.now();
+
+            let mut interval =
+                Interval::new(&clock.handle(), start, period).with_missed_tick_behavior(behavior);
+
+            assert_ticks(&mut interval, &[start]);
+
+            // Stall for more than three periods
+            clock.advance(ms(350));
+            let missed: Vec<_> = missed[1..].iter().map(|&v| start + ms(v)).collect();
+            assert_ticks(&mut interval, &missed);
+
+            clock.advance(ms(next - 350 - 1));
+            assert_ticks(&mut interval, &[]);
+
+            clock.advance(ms(1));
+            assert_ticks(&mut interval, &[start + ms(next)]);
+        }
+    }
+
     #[test]
     fn interval_burst() {
         let timers = Timers::new();
@@ -0,0 +1,72 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+
+use super::{Clock, Timers, TimersHandle};
+
+/// A virtual clock which only advances when told to.
+///
+/// Timers created from the [`handle`](Self::handle) fire deterministically as the clock is
+/// advanced, without any real waiting.
+#[derive(Clone)]
+pub struct MockClock {
+    now: Arc<Mutex<Instant>>,
+    timers: Arc<Mutex<Timers>>,
+}
+
+impl MockClock {
+    /// Creates a new clock starting at the current time
+    pub fn new() -> Self {
+        let now = Arc::new(Mutex::new(Instant::now()));
+
+        Self {
+            timers: Arc::new(Mutex::new(Timers::with_clock(Clock::Mock(now.clone())))),
+            now,
+        }
+    }
+
+    /// Returns the current virtual time
+    pub fn now(&self) -> Instant {
+        *self.now.lock()
+    }
+
+    /// Returns a handle for creating timers driven by this clock
+    pub fn handle(&self) -> TimersHandle {
+        self.timers.lock().handle()
+    }
+
+    /// Returns the deadline of the next timer to fire
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.timers.lock().next_deadline()
+    }
+
+    /// Advances the clock, firing all timers which expired.
+    ///
+    /// Returns the next deadline, if any.
+    pub fn advance(&self, duration: Duration) -> Option<Instant> {
+        let now = {
+            let mut now = self.now.lock();
+            *now += duration;
+            *now
+        };
+
+        self.timers.lock().tick(now)
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for MockClock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockClock")
+            .field("now", &self.now())
+            .finish()
+    }
+}
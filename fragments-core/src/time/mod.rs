@@ -1,4 +1,5 @@
 use std::{
+    cell::RefCell,
     collections::BTreeSet,
     eprintln,
     marker::PhantomPinned,
@@ -21,17 +22,67 @@ use parking_lot::Mutex;
 use pin_project::{pin_project, pinned_drop};
 use slotmap::new_key_type;
 mod interval;
+mod mock;
+mod timeout;
 
-pub use interval::{interval, interval_at, Interval};
+pub use interval::{interval, interval_at, Interval, MissedTickBehavior};
+pub use mock::MockClock;
+pub use timeout::{timeout, timeout_at, Deadline, Elapsed, Timeout};
 
 pub static GLOBAL_TIMER: Lazy<TimersHandle> = Lazy::new(Timers::start);
 
+thread_local! {
+    static CURRENT: RefCell<Option<TimersHandle>> = RefCell::new(None);
+}
+
+/// Returns the timers used by the free functions such as [`sleep`] on the current thread.
+///
+/// Defaults to [`GLOBAL_TIMER`] unless another handle has been [entered](enter).
+pub fn current() -> TimersHandle {
+    CURRENT.with(|v| v.borrow().clone().unwrap_or_else(|| GLOBAL_TIMER.clone()))
+}
+
+/// Uses `handle` for the timers created on the current thread until the guard is dropped.
+///
+/// The executor enters the timers of a scope while polling its effects.
+pub fn enter(handle: TimersHandle) -> EnterGuard {
+    let prev = CURRENT.with(|v| v.borrow_mut().replace(handle));
+    EnterGuard { prev }
+}
+
+/// Restores the previously entered timers when dropped
+pub struct EnterGuard {
+    prev: Option<TimersHandle>,
+}
+
+impl Drop for EnterGuard {
+    fn drop(&mut self) {
+        CURRENT.with(|v| *v.borrow_mut() = self.prev.take());
+    }
+}
+
 pub fn sleep_until(deadline: Instant) -> Sleep {
-    Sleep::new(&GLOBAL_TIMER, deadline)
+    current().sleep_until(deadline)
 }
 
 pub fn sleep(duration: Duration) -> Sleep {
-    Sleep::new(&GLOBAL_TIMER, Instant::now() + duration)
+    current().sleep(duration)
+}
+
+/// The source of the current time for a set of timers
+#[derive(Debug, Clone)]
+enum Clock {
+    System,
+    Mock(Arc<Mutex<Instant>>),
+}
+
+impl Clock {
+    fn now(&self) -> Instant {
+        match self {
+            Clock::System => Instant::now(),
+            Clock::Mock(now) => *now.lock(),
+        }
+    }
 }
 
 struct TimerEntry {
@@ -84,22 +135,82 @@ impl Inner {
 #[derive(Clone)]
 pub struct TimersHandle {
     inner: Arc<Mutex<Inner>>,
+    clock: Clock,
+}
+
+impl TimersHandle {
+    /// Returns the current time of the clock driving the timers
+    pub fn now(&self) -> Instant {
+        self.clock.now()
+    }
+
+    pub fn sleep_until(&self, deadline: Instant) -> Sleep {
+        Sleep::new(self, deadline)
+    }
+
+    pub fn sleep(&self, duration: Duration) -> Sleep {
+        Sleep::new(self, self.now() + duration)
+    }
+
+    pub fn interval(&self, period: Duration) -> Interval {
+        Interval::new(self, self.now(), period)
+    }
+
+    pub fn interval_at(&self, start: Instant, period: Duration) -> Interval {
+        Interval::new(self, start, period)
+    }
+
+    pub fn timeout<F: Future>(&self, duration: Duration, future: F) -> Timeout<F> {
+        Timeout::new(future, self.sleep(duration))
+    }
+
+    pub fn timeout_at<F: Future>(&self, deadline: Instant, future: F) -> Timeout<F> {
+        Timeout::new(future, self.sleep_until(deadline))
+    }
+}
+
+impl std::fmt::Debug for TimersHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TimersHandle")
+            .field("clock", &self.clock)
+            .finish()
+    }
 }
 
 pub struct Timers {
     inner: Arc<Mutex<Inner>>,
+    clock: Clock,
 }
 
 impl Timers {
     pub fn new() -> Self {
+        Self::with_clock(Clock::System)
+    }
+
+    fn with_clock(clock: Clock) -> Self {
         Self {
             inner: Arc::new(Mutex::new(Inner {
                 heap: BTreeSet::new(),
                 waker: noop_waker(),
             })),
+            clock,
         }
     }
 
+    /// Sets the waker which is woken when a new timer is registered.
+    ///
+    /// Allows driving the timers from an existing loop, such as the UI event loop, instead of a
+    /// dedicated thread. The loop should call [`Self::tick`] and then sleep until the returned
+    /// deadline or until woken.
+    pub fn set_waker(&mut self, waker: Waker) {
+        self.inner.lock().waker = waker;
+    }
+
+    /// Returns the deadline of the next timer to fire
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.inner.lock().heap.first().map(|v| v.deadline)
+    }
+
     /// Advances the timers, returning the next deadline
     pub fn tick(&mut self, time: Instant) -> Option<Instant> {
         let mut shared = self.inner.lock();
@@ -138,7 +249,7 @@ impl Timers {
             thread_id: thread::current(),
         });
 
-        self.inner.lock().waker = futures::task::waker(waker);
+        self.set_waker(futures::task::waker(waker));
 
         loop {
             let now = Instant::now();
@@ -156,6 +267,7 @@ impl Timers {
     pub fn handle(&self) -> TimersHandle {
         TimersHandle {
             inner: self.inner.clone(),
+            clock: self.clock.clone(),
         }
     }
 }
@@ -164,6 +276,7 @@ impl Timers {
 /// Sleep future
 pub struct Sleep {
     shared: Arc<Mutex<Inner>>,
+    clock: Clock,
     timer: TimerEntry,
     deadline: Instant,
     registered: bool,
@@ -181,6 +294,7 @@ impl Sleep {
     pub(crate) fn new(handle: &TimersHandle, deadline: Instant) -> Self {
         Self {
             shared: handle.inner.clone(),
+            clock: handle.clock.clone(),
             timer: TimerEntry {
                 waker: Mutex::new(noop_waker()),
                 finished: AtomicBool::new(false),
@@ -227,6 +341,14 @@ impl Future for Sleep {
             .compare_exchange(true, false, Ordering::Release, Ordering::Relaxed)
             .is_ok()
         {
+            Poll::Ready(())
+        } else if self.deadline <= self.clock.now() {
+            // Complete expired timers right away rather than waiting for the timers to be ticked,
+            // which a mock clock only does when advanced
+            if self.registered {
+                self.unregister();
+            }
+
             Poll::Ready(())
         } else if !self.registered {
             *self.timer.waker.lock() = cx.waker().clone();
@@ -265,7 +387,7 @@ pub(crate) fn assert_dur(found: Duration, expected: Duration, msg: &str) {
 
 #[cfg(test)]
 mod test {
-    use std::{eprintln, time::Duration};
+    use std::{eprintln, sync::atomic::AtomicUsize, time::Duration};
 
     use futures::{stream, FutureExt, StreamExt};
 
@@ -273,90 +395,184 @@ mod test {
 
     #[test]
     fn sleep() {
-        let timers = Timers::new();
-
-        let handle = timers.handle();
+        let clock = MockClock::new();
+        let handle = clock.handle();
+        let start = clock.now();
 
-        thread::spawn(move || timers.run_blocking());
+        let mut seq = Box::pin(async move {
+            Sleep::new(&handle, start + Duration::from_millis(500)).await;
 
-        let now = Instant::now();
-        futures::executor::block_on(async move {
-            Sleep::new(&handle, Instant::now() + Duration::from_millis(500)).await;
+            let now = handle.now();
+            Sleep::new(&handle, now + Duration::from_millis(1000)).await;
 
-            eprintln!("Timer 1 finished");
+            // Expired timers complete right away
+            Sleep::new(&handle, now - Duration::from_millis(100)).await;
+        });
 
-            let now = Instant::now();
-            Sleep::new(&handle, Instant::now() + Duration::from_millis(1000)).await;
+        assert_eq!(seq.as_mut().now_or_never(), None);
 
-            Sleep::new(&handle, now - Duration::from_millis(100)).await;
+        clock.advance(Duration::from_millis(499));
+        assert_eq!(seq.as_mut().now_or_never(), None);
 
-            eprintln!("Expired timer finished")
-        });
+        clock.advance(Duration::from_millis(1));
+        assert_eq!(seq.as_mut().now_or_never(), None);
 
-        assert_dur(now.elapsed(), Duration::from_millis(500 + 1000), "seq");
+        clock.advance(Duration::from_millis(999));
+        assert_eq!(seq.as_mut().now_or_never(), None);
 
-        eprintln!("Done");
+        clock.advance(Duration::from_millis(1));
+        assert_eq!(seq.now_or_never(), Some(()));
+        assert_eq!(clock.now(), start + Duration::from_millis(500 + 1000));
     }
 
     #[test]
     fn sleep_join() {
-        let timers = Timers::new();
+        let clock = MockClock::new();
+        let handle = clock.handle();
+        let start = clock.now();
 
-        let handle = timers.handle();
+        let mut join = Box::pin(async move {
+            let sleep_1 = Sleep::new(&handle, start + Duration::from_millis(500));
+            let sleep_2 = Sleep::new(&handle, start + Duration::from_millis(1000));
+            let sleep_3 = Sleep::new(&handle, start - Duration::from_millis(100));
 
-        thread::spawn(move || timers.run_blocking());
+            futures::join!(sleep_1, sleep_2, sleep_3);
+        });
 
-        let now = Instant::now();
-        futures::executor::block_on(async move {
-            let sleep_1 = Sleep::new(&handle, Instant::now() + Duration::from_millis(500));
+        assert_eq!(join.as_mut().now_or_never(), None);
+        assert_eq!(
+            clock.next_deadline(),
+            Some(start + Duration::from_millis(500))
+        );
 
-            eprintln!("Timer 1 finished");
+        clock.advance(Duration::from_millis(500));
+        assert_eq!(join.as_mut().now_or_never(), None);
 
-            let now = Instant::now();
-            let sleep_2 = Sleep::new(&handle, Instant::now() + Duration::from_millis(1000));
+        clock.advance(Duration::from_millis(500));
+        assert_eq!(join.now_or_never(), Some(()));
+        assert_eq!(clock.next_deadline(), None);
+    }
 
-            let sleep_3 = Sleep::new(&handle, now - Duration::from_millis(100));
+    #[test]
+    fn sleep_race() {
+        let clock = MockClock::new();
+        let handle = clock.handle();
+        let start = clock.now();
 
-            futures::join!(sleep_1, sleep_2, sleep_3);
+        let mut race = Box::pin(async move {
+            {
+                let mut sleep_1 = Sleep::new(&handle, start + Duration::from_millis(500)).fuse();
+                let mut sleep_2 = Sleep::new(&handle, start + Duration::from_millis(1000)).fuse();
 
-            eprintln!("Expired timer finished")
+                futures::select!(_ = sleep_1 => {}, _ = sleep_2 => {});
+            }
+
+            Sleep::new(&handle, handle.now() + Duration::from_millis(1500)).await;
+
+            let _never_polled = Sleep::new(&handle, handle.now() + Duration::from_millis(2000));
+            futures::pin_mut!(_never_polled);
         });
 
-        assert_dur(now.elapsed(), Duration::from_millis(1000), "join");
-        eprintln!("Done");
+        assert_eq!(race.as_mut().now_or_never(), None);
+
+        clock.advance(Duration::from_millis(500));
+        assert_eq!(race.as_mut().now_or_never(), None);
+
+        // The losing timer was removed when dropped
+        assert_eq!(
+            clock.next_deadline(),
+            Some(start + Duration::from_millis(2000))
+        );
+
+        clock.advance(Duration::from_millis(1500));
+        assert_eq!(race.now_or_never(), Some(()));
+        assert_eq!(clock.next_deadline(), None);
     }
 
     #[test]
-    fn sleep_race() {
-        let timers = Timers::new();
-
+    fn manual_tick() {
+        let mut timers = Timers::new();
         let handle = timers.handle();
 
-        thread::spawn(move || timers.run_blocking());
+        struct CountWaker(AtomicUsize);
 
-        let now = Instant::now();
-        futures::executor::block_on(async move {
-            {
-                let mut sleep_1 =
-                    Sleep::new(&handle, Instant::now() + Duration::from_millis(500)).fuse();
+        impl ArcWake for CountWaker {
+            fn wake_by_ref(arc_self: &Arc<Self>) {
+                arc_self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
 
-                eprintln!("Timer 1 finished");
+        let count = Arc::new(CountWaker(AtomicUsize::new(0)));
+        timers.set_waker(futures::task::waker(count.clone()));
 
-                let mut sleep_2 =
-                    Sleep::new(&handle, Instant::now() + Duration::from_millis(1000)).fuse();
+        let now = Instant::now();
+        let mut sleep = Box::pin(handle.sleep_until(now + Duration::from_millis(100)));
+        assert_eq!(timers.next_deadline(), None);
+
+        // Registering the timer wakes the loop, so that it can wait for the new deadline
+        assert_eq!(sleep.as_mut().now_or_never(), None);
+        assert_eq!(count.0.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            timers.next_deadline(),
+            Some(now + Duration::from_millis(100))
+        );
+
+        assert_eq!(
+            timers.tick(now + Duration::from_millis(50)),
+            Some(now + Duration::from_millis(100))
+        );
+        assert_eq!(timers.tick(now + Duration::from_millis(100)), None);
+        assert_eq!(sleep.now_or_never(), Some(()));
+    }
 
-                futures::select!(_ = sleep_1 => {}, _ = sleep_2 => {});
-            }
+    #[test]
+    fn mock_sleep() {
+        let clock = MockClock::new();
+        let handle = clock.handle();
+        let start = clock.now();
+
+        let mut sleep_1 = Box::pin(handle.sleep(Duration::from_millis(500)));
+        let mut sleep_2 = Box::pin(handle.sleep_until(start + Duration::from_secs(10)));
+        assert_eq!(sleep_1.as_mut().now_or_never(), None);
+        assert_eq!(sleep_2.as_mut().now_or_never(), None);
+
+        assert_eq!(
+            clock.advance(Duration::from_millis(499)),
+            Some(start + Duration::from_millis(500))
+        );
+        assert_eq!(sleep_1.as_mut().now_or_never(), None);
+
+        assert_eq!(
+            clock.advance(Duration::from_millis(1)),
+            Some(start + Duration::from_secs(10))
+        );
+        assert_eq!(sleep_1.now_or_never(), Some(()));
+
+        assert_eq!(clock.advance(Duration::from_secs(60)), None);
+        assert_eq!(sleep_2.now_or_never(), Some(()));
+        assert_eq!(clock.now(), start + Duration::from_millis(60_500));
+    }
+
+    #[test]
+    fn mock_enter() {
+        let clock = MockClock::new();
 
-            Sleep::new(&handle, Instant::now() + Duration::from_millis(1500)).await;
+        {
+            let _guard = enter(clock.handle());
+            let mut sleep = Box::pin(super::sleep(Duration::from_secs(1)));
+            let mut interval = interval(Duration::from_secs(1));
 
-            let _never_polled = Sleep::new(&handle, Instant::now() + Duration::from_millis(2000));
-            futures::pin_mut!(_never_polled);
-        });
+            assert_eq!(sleep.as_mut().now_or_never(), None);
+            assert_eq!(interval.next().now_or_never(), Some(Some(clock.now())));
+            assert_eq!(interval.next().now_or_never(), None);
 
-        assert_dur(now.elapsed(), Duration::from_millis(2000), "race");
+            clock.advance(Duration::from_secs(1));
+            assert_eq!(sleep.now_or_never(), Some(()));
+            assert_eq!(interval.next().now_or_never(), Some(Some(clock.now())));
+        }
 
-        eprintln!("Done");
+        // The previous timers are restored
+        assert!(matches!(current().clock, Clock::System));
     }
 
     #[test]
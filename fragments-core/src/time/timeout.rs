@@ -0,0 +1,167 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use futures::Future;
+use pin_project::pin_project;
+use thiserror::Error;
+
+use super::{current, Sleep, TimersHandle};
+
+/// Returned by [`Timeout`] when the deadline elapsed before the future completed
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("Deadline has elapsed")]
+pub struct Elapsed(());
+
+/// Requires a future to complete before `duration` has elapsed
+pub fn timeout<F: Future>(duration: Duration, future: F) -> Timeout<F> {
+    current().timeout(duration, future)
+}
+
+/// Requires a future to complete before `deadline`
+pub fn timeout_at<F: Future>(deadline: Instant, future: F) -> Timeout<F> {
+    current().timeout_at(deadline, future)
+}
+
+/// Future returned by [`timeout`] and [`timeout_at`]
+#[pin_project]
+#[derive(Debug)]
+pub struct Timeout<F> {
+    #[pin]
+    future: F,
+    #[pin]
+    sleep: Sleep,
+}
+
+impl<F> Timeout<F> {
+    pub(crate) fn new(future: F, sleep: Sleep) -> Self {
+        Self { future, sleep }
+    }
+
+    /// Returns the inner future
+    pub fn into_inner(self) -> F {
+        self.future
+    }
+}
+
+impl<F: Future> Future for Timeout<F> {
+    type Output = Result<F::Output, Elapsed>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let p = self.project();
+
+        // Prefer the value if both are ready
+        if let Poll::Ready(v) = p.future.poll(cx) {
+            return Poll::Ready(Ok(v));
+        }
+
+        p.sleep.poll(cx).map(|_| Err(Elapsed(())))
+    }
+}
+
+/// A point in time which several operations must complete before.
+///
+/// Allows a sequence of async operations, such as loading and then decoding an asset, to share a
+/// single time limit.
+#[derive(Debug, Clone)]
+pub struct Deadline {
+    instant: Instant,
+    timers: TimersHandle,
+}
+
+impl Deadline {
+    /// Creates a deadline at `instant` using the current timers
+    pub fn new(instant: Instant) -> Self {
+        Self {
+            instant,
+            timers: current(),
+        }
+    }
+
+    /// Creates a deadline `duration` from now
+    pub fn after(duration: Duration) -> Self {
+        let timers = current();
+
+        Self {
+            instant: timers.now() + duration,
+            timers,
+        }
+    }
+
+    pub fn instant(&self) -> Instant {
+        self.instant
+    }
+
+    /// Returns the time left until the deadline, or zero if it has elapsed
+    pub fn remaining(&self) -> Duration {
+        self.instant.saturating_duration_since(self.timers.now())
+    }
+
+    pub fn is_elapsed(&self) -> bool {
+        self.timers.now() >= self.instant
+    }
+
+    /// Requires `future` to complete before the deadline
+    pub fn timeout<F: Future>(&self, future: F) -> Timeout<F> {
+        self.timers.timeout_at(self.instant, future)
+    }
+
+    /// Returns a future which completes at the deadline
+    pub fn sleep(&self) -> Sleep {
+        self.timers.sleep_until(self.instant)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use futures::{future, FutureExt};
+
+    use crate::time::{enter, MockClock};
+
+    use super::*;
+
+    #[test]
+    fn timeout() {
+        let clock = MockClock::new();
+        let _guard = enter(clock.handle());
+
+        let mut pending = Box::pin(super::timeout(
+            Duration::from_secs(1),
+            future::pending::<()>(),
+        ));
+        assert_eq!(pending.as_mut().now_or_never(), None);
+
+        clock.advance(Duration::from_millis(999));
+        assert_eq!(pending.as_mut().now_or_never(), None);
+
+        clock.advance(Duration::from_millis(1));
+        assert_eq!(pending.now_or_never(), Some(Err(Elapsed(()))));
+
+        let ready = super::timeout(Duration::ZERO, future::ready(5));
+        assert_eq!(ready.now_or_never(), Some(Ok(5)));
+    }
+
+    #[test]
+    fn deadline() {
+        let clock = MockClock::new();
+        let _guard = enter(clock.handle());
+
+        let deadline = Deadline::after(Duration::from_secs(2));
+        let (tx, rx) = futures::channel::oneshot::channel();
+
+        let mut first = Box::pin(deadline.timeout(rx));
+        let mut second = Box::pin(deadline.timeout(future::pending::<()>()));
+
+        clock.advance(Duration::from_millis(500));
+        tx.send(1).unwrap();
+        assert_eq!(first.as_mut().now_or_never(), Some(Ok(Ok(1))));
+        assert_eq!(deadline.remaining(), Duration::from_millis(1500));
+
+        assert_eq!(second.as_mut().now_or_never(), None);
+        clock.advance(Duration::from_millis(1500));
+        assert!(deadline.is_elapsed());
+        assert_eq!(second.now_or_never(), Some(Err(Elapsed(()))));
+    }
+}
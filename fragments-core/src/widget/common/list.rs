@@ -0,0 +1,303 @@
+use std::{collections::HashMap, hash::Hash};
+
+use flax::{name, Entity};
+
+use crate::{
+    components::ordered_children,
+    signal::{Map, MutableVec, MutableVecSignal, Signal, VecDiff},
+    Scope, Widget,
+};
+
+/// Renders a widget for each item of a keyed list.
+///
+/// Children are reconciled by key when the list changes: new items are attached, removed items
+/// are detached, and moved items are reordered while keeping their state. Each item is rendered
+/// once per key, so an item which changes but keeps its key is not rendered again. Use a signal
+/// inside the item to update it in place.
+pub struct For<S, K, R> {
+    diffs: S,
+    key: K,
+    render: R,
+}
+
+impl<T, K, R> For<MutableVecSignal<T>, K, R> {
+    /// Renders the items of a [`MutableVec`], following each incremental change
+    pub fn new(items: &MutableVec<T>, key: K, render: R) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            diffs: items.signal(),
+            key,
+            render,
+        }
+    }
+}
+
+type DiffFn<T> = fn(Vec<T>) -> Vec<VecDiff<T>>;
+
+fn replace<T>(values: Vec<T>) -> Vec<VecDiff<T>> {
+    vec![VecDiff::Replace { values }]
+}
+
+impl<S, T, K, R> For<Map<S, DiffFn<T>>, K, R> {
+    /// Renders each list yielded by a signal.
+    ///
+    /// Each list is reconciled with the previous one by key.
+    pub fn from_signal(items: S, key: K, render: R) -> Self
+    where
+        S: for<'x> Signal<'x, Item = Vec<T>>,
+    {
+        Self {
+            diffs: items.map(replace as DiffFn<T>),
+            key,
+            render,
+        }
+    }
+}
+
+impl<S, T, K, Q, R, W> Widget for For<S, K, R>
+where
+    S: 'static + for<'x> Signal<'x, Item = Vec<VecDiff<T>>>,
+    T: 'static,
+    K: 'static + FnMut(&T) -> Q,
+    Q: 'static + Hash + Eq,
+    R: 'static + FnMut(&T) -> W,
+    W: Widget,
+{
+    fn mount(self, scope: &mut Scope) {
+        let mut list = KeyedChildren {
+            children: Vec::new(),
+            key: self.key,
+            render: self.render,
+        };
+
+        scope.use_signal(self.diffs, move |scope, diffs| {
+            for diff in diffs {
+                list.apply(scope, diff);
+            }
+
+            // Attaching appends to the children, so restore the order of the list
+            let ids = list.children.iter().map(|&(_, id)| id).collect();
+            scope.set(ordered_children(), ids);
+        });
+
+        scope.set(name(), tynm::type_name::<Self>());
+    }
+}
+
+/// The mounted children of a [`For`] in the order of the list
+struct KeyedChildren<Q, K, R> {
+    children: Vec<(Q, Entity)>,
+    key: K,
+    render: R,
+}
+
+impl<Q, K, R> KeyedChildren<Q, K, R> {
+    fn mount<T, W>(&mut self, scope: &mut Scope, value: &T) -> (Q, Entity)
+    where
+        K: FnMut(&T) -> Q,
+        R: FnMut(&T) -> W,
+        W: Widget,
+    {
+        ((self.key)(value), scope.attach((self.render)(value)))
+    }
+
+    fn apply<T, W>(&mut self, scope: &mut Scope, diff: VecDiff<T>)
+    where
+        K: FnMut(&T) -> Q,
+        Q: Hash + Eq,
+        R: FnMut(&T) -> W,
+        W: Widget,
+    {
+        match diff {
+            VecDiff::Push { value } => {
+                let child = self.mount(scope, &value);
+                self.children.push(child);
+            }
+            VecDiff::Insert { index, value } => {
+                let child = self.mount(scope, &value);
+                self.children.insert(index, child);
+            }
+            VecDiff::Remove { index } => {
+                let (_, id) = self.children.remove(index);
+                scope.detach(id);
+            }
+            VecDiff::Update { index, value } => {
+                if (self.key)(&value) != self.children[index].0 {
+                    let child = self.mount(scope, &value);
+                    let (_, id) = std::mem::replace(&mut self.children[index], child);
+                    scope.detach(id);
+                }
+            }
+            VecDiff::Move { from, to } => {
+                let child = self.children.remove(from);
+                self.children.insert(to, child);
+            }
+            VecDiff::Replace { values } => {
+                // Keys are expected to be unique, a duplicate is rendered anew
+                let mut old = HashMap::new();
+                for (key, id) in self.children.drain(..) {
+                    if let Some(duplicate) = old.insert(key, id) {
+                        scope.detach(duplicate);
+                    }
+                }
+
+                self.children = values
+                    .iter()
+                    .map(|value| {
+                        let key = (self.key)(value);
+                        match old.remove(&key) {
+                            Some(id) => (key, id),
+                            None => (key, scope.attach((self.render)(value))),
+                        }
+                    })
+                    .collect();
+
+                for id in old.into_values() {
+                    scope.detach(id);
+                }
+            }
+            VecDiff::Clear => {
+                for (_, id) in self.children.drain(..) {
+                    scope.detach(id);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        signal::Mutable,
+        testing::{
+            fixtures::{labels, Label},
+            TestApp,
+        },
+    };
+
+    use super::*;
+
+    type Item = (u32, &'static str);
+
+    fn key(item: &Item) -> u32 {
+        item.0
+    }
+
+    fn render(item: &Item) -> Label {
+        Label(item.1)
+    }
+
+    #[test]
+    fn for_each() {
+        let mut app = TestApp::new();
+        let items = MutableVec::new(vec![(1, "a"), (2, "b")]);
+        let id = app.mount(For::new(&items, key, render));
+
+        assert_eq!(labels(&app, id), ["a", "b"]);
+        let [a, b] = app.children(id)[..] else {
+            panic!("Expected two children")
+        };
+
+        items.write().push((3, "c"));
+        app.run_until_stalled();
+        assert_eq!(labels(&app, id), ["a", "b", "c"]);
+        let c = app.children(id)[2];
+
+        items.write().insert(0, (4, "d"));
+        app.run_until_stalled();
+        assert_eq!(labels(&app, id), ["d", "a", "b", "c"]);
+        let d = app.children(id)[0];
+        assert_eq!(app.children(id), [d, a, b, c]);
+
+        items.write().remove(2);
+        app.run_until_stalled();
+        assert_eq!(app.children(id), [d, a, c]);
+        assert!(!app.world().is_alive(b));
+
+        // Moved items keep their entity
+        items.write().move_item(0, 2);
+        app.run_until_stalled();
+        assert_eq!(labels(&app, id), ["a", "c", "d"]);
+        assert_eq!(app.children(id), [a, c, d]);
+
+        // Several changes are applied as one batch
+        {
+            let mut items = items.write();
+            items.push((5, "e"));
+            items.remove(0);
+        }
+        app.run_until_stalled();
+        assert_eq!(labels(&app, id), ["c", "d", "e"]);
+        assert!(!app.world().is_alive(a));
+    }
+
+    #[test]
+    fn for_replace() {
+        let mut app = TestApp::new();
+        let items = MutableVec::new(vec![(1, "a"), (2, "b"), (3, "c")]);
+        let id = app.mount(For::new(&items, key, render));
+
+        let [a, b, c] = app.children(id)[..] else {
+            panic!("Expected three children")
+        };
+
+        // Reordered and partially overlapping keys
+        items.write().replace(vec![(3, "c"), (4, "d"), (1, "a")]);
+        app.run_until_stalled();
+
+        assert_eq!(labels(&app, id), ["c", "d", "a"]);
+        let d = app.children(id)[1];
+        assert_eq!(app.children(id), [c, d, a]);
+        assert!(!app.world().is_alive(b));
+        assert_eq!(app.find_by_name(&tynm::type_name::<Label>()).len(), 3);
+
+        items.write().clear();
+        app.run_until_stalled();
+        assert_eq!(app.children(id), []);
+        assert!(app.find_by_name(&tynm::type_name::<Label>()).is_empty());
+    }
+
+    #[test]
+    fn for_update() {
+        let mut app = TestApp::new();
+        let items = MutableVec::new(vec![(1, "a"), (2, "b")]);
+        let id = app.mount(For::new(&items, key, render));
+
+        let [a, b] = app.children(id)[..] else {
+            panic!("Expected two children")
+        };
+
+        // The same key is not rendered again
+        items.write().set(0, (1, "x"));
+        app.run_until_stalled();
+        assert_eq!(labels(&app, id), ["a", "b"]);
+        assert_eq!(app.children(id), [a, b]);
+
+        // A changed key is
+        items.write().set(1, (3, "c"));
+        app.run_until_stalled();
+        assert_eq!(labels(&app, id), ["a", "c"]);
+        let c = app.children(id)[1];
+        assert_ne!(c, b);
+        assert!(!app.world().is_alive(b));
+    }
+
+    #[test]
+    fn for_from_signal() {
+        let mut app = TestApp::new();
+        let items = Mutable::new(vec![(1, "a"), (2, "b")]);
+        let id = app.mount(For::from_signal(items.signal(), key, render));
+
+        let [a, b] = app.children(id)[..] else {
+            panic!("Expected two children")
+        };
+
+        items.set(vec![(2, "b"), (3, "c"), (1, "a")]);
+        app.run_until_stalled();
+        assert_eq!(labels(&app, id), ["b", "c", "a"]);
+        let c = app.children(id)[1];
+        assert_eq!(app.children(id), [b, c, a]);
+    }
+}
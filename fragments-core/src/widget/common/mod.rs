@@ -9,6 +9,17 @@ use futures::{Future, Stream};
 
 use super::WidgetCollection;
 
+mod list;
+mod show;
+mod suspense;
+
+pub use list::For;
+pub use show::{Show, Switch};
+pub use suspense::{Retry, Suspense};
+
+/// Mounts the widget resolved by a future.
+///
+/// Nothing is shown until then, see [`Suspense`] for loading and error states.
 pub struct AsyncWidget<F>(pub F);
 
 impl<F> Widget for AsyncWidget<F>
@@ -0,0 +1,241 @@
+use flax::{name, Entity};
+
+use crate::{components::ordered_children, signal::Signal, Scope, Widget};
+
+/// Renders `then` while `when` is true, and `otherwise` while it is false.
+///
+/// The branch is only re-mounted when the condition changes, so repeated values keep the state of
+/// the mounted branch.
+pub struct Show<S, T, O> {
+    pub when: S,
+    pub then: T,
+    pub otherwise: O,
+}
+
+impl<S, T, O, W, W2> Widget for Show<S, T, O>
+where
+    S: 'static + for<'x> Signal<'x, Item = bool>,
+    T: 'static + FnMut() -> W,
+    O: 'static + FnMut() -> W2,
+    W: Widget,
+    W2: Widget,
+{
+    fn mount(self, scope: &mut Scope) {
+        let mut then = self.then;
+        let mut otherwise = self.otherwise;
+        let mut current: Option<(bool, Entity)> = None;
+
+        scope.use_signal(self.when, move |scope, when| {
+            if let Some((prev, id)) = current {
+                if prev == when {
+                    return;
+                }
+
+                scope.detach(id);
+            }
+
+            let id = if when {
+                scope.attach(then())
+            } else {
+                scope.attach(otherwise())
+            };
+
+            // Forget the detached branch
+            scope.set(ordered_children(), vec![id]);
+            current = Some((when, id));
+        });
+
+        scope.set(name(), tynm::type_name::<Self>());
+    }
+}
+
+type Render = Box<dyn FnMut() -> Box<dyn Widget>>;
+
+/// Renders the case matching the value of a signal, or the fallback if no case matches.
+///
+/// Like [`Show`], the branch is only re-mounted when a different case is selected.
+///
+/// Not to be confused with [`signal::Switch`](crate::signal::Switch), which switches between
+/// signals.
+pub struct Switch<S, V> {
+    value: S,
+    cases: Vec<(V, Render)>,
+    fallback: Option<Render>,
+}
+
+impl<S, V> Switch<S, V>
+where
+    S: for<'x> Signal<'x, Item = V>,
+    V: PartialEq,
+{
+    pub fn new(value: S) -> Self {
+        Self {
+            value,
+            cases: Vec::new(),
+            fallback: None,
+        }
+    }
+
+    /// Renders `render` when the signal yields `value`.
+    ///
+    /// The first matching case is used.
+    pub fn case<W: 'static + Widget>(
+        mut self,
+        value: V,
+        mut render: impl 'static + FnMut() -> W,
+    ) -> Self {
+        self.cases.push((
+            value,
+            Box::new(move || Box::new(render()) as Box<dyn Widget>),
+        ));
+        self
+    }
+
+    /// Renders `render` when no case matches. Nothing is rendered by default.
+    pub fn fallback<W: 'static + Widget>(
+        mut self,
+        mut render: impl 'static + FnMut() -> W,
+    ) -> Self {
+        self.fallback = Some(Box::new(move || Box::new(render()) as Box<dyn Widget>));
+        self
+    }
+}
+
+impl<S, V> Widget for Switch<S, V>
+where
+    S: 'static + for<'x> Signal<'x, Item = V>,
+    V: 'static + PartialEq,
+{
+    fn mount(self, scope: &mut Scope) {
+        let mut cases = self.cases;
+        let mut fallback = self.fallback;
+        // The index of the selected case, or `None` for the fallback
+        let mut current: Option<(Option<usize>, Option<Entity>)> = None;
+
+        scope.use_signal(self.value, move |scope, value| {
+            let selected = cases.iter().position(|(case, _)| *case == value);
+
+            if let Some((prev, id)) = current {
+                if prev == selected {
+                    return;
+                }
+
+                if let Some(id) = id {
+                    scope.detach(id);
+                }
+            }
+
+            let render = match selected {
+                Some(index) => Some(&mut cases[index].1),
+                None => fallback.as_mut(),
+            };
+
+            let id = render.map(|render| scope.attach(render()));
+            scope.set(ordered_children(), id.into_iter().collect());
+            current = Some((selected, id));
+        });
+
+        scope.set(name(), tynm::type_name::<Self>());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        signal::Mutable,
+        testing::{
+            fixtures::{labels, Label},
+            TestApp,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn show() {
+        let mut app = TestApp::new();
+        let when = Mutable::new(true);
+        let id = app.mount(Show {
+            when: when.signal(),
+            then: || Label("then"),
+            otherwise: || Label("otherwise"),
+        });
+
+        assert_eq!(labels(&app, id), ["then"]);
+        let [then] = app.children(id)[..] else {
+            panic!("Expected one child")
+        };
+
+        // The same branch is kept
+        when.set(true);
+        app.run_until_stalled();
+        assert_eq!(app.children(id), [then]);
+
+        when.set(false);
+        app.run_until_stalled();
+        assert_eq!(labels(&app, id), ["otherwise"]);
+        assert!(!app.world().is_alive(then));
+
+        when.set(true);
+        app.run_until_stalled();
+        assert_eq!(labels(&app, id), ["then"]);
+        assert_eq!(app.find_by_name(&tynm::type_name::<Label>()).len(), 1);
+    }
+
+    #[test]
+    fn switch() {
+        let mut app = TestApp::new();
+        let value = Mutable::new(1);
+        let id = app.mount(
+            Switch::new(value.signal())
+                .case(1, || Label("one"))
+                .case(2, || Label("two")),
+        );
+
+        assert_eq!(labels(&app, id), ["one"]);
+        let [one] = app.children(id)[..] else {
+            panic!("Expected one child")
+        };
+
+        value.set(1);
+        app.run_until_stalled();
+        assert_eq!(app.children(id), [one]);
+
+        value.set(2);
+        app.run_until_stalled();
+        assert_eq!(labels(&app, id), ["two"]);
+        assert!(!app.world().is_alive(one));
+
+        // Nothing is rendered without a fallback
+        value.set(3);
+        app.run_until_stalled();
+        assert!(labels(&app, id).is_empty());
+        assert!(app.find_by_name(&tynm::type_name::<Label>()).is_empty());
+    }
+
+    #[test]
+    fn switch_fallback() {
+        let mut app = TestApp::new();
+        let value = Mutable::new(3);
+        let id = app.mount(
+            Switch::new(value.signal())
+                .case(1, || Label("one"))
+                .fallback(|| Label("fallback")),
+        );
+
+        assert_eq!(labels(&app, id), ["fallback"]);
+        let [fallback] = app.children(id)[..] else {
+            panic!("Expected one child")
+        };
+
+        // Any value without a case keeps the fallback
+        value.set(4);
+        app.run_until_stalled();
+        assert_eq!(app.children(id), [fallback]);
+
+        value.set(1);
+        app.run_until_stalled();
+        assert_eq!(labels(&app, id), ["one"]);
+        assert!(!app.world().is_alive(fallback));
+    }
+}
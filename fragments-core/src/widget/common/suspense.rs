@@ -0,0 +1,382 @@
+use std::{cell::RefCell, rc::Rc, sync::Arc, time::Duration};
+
+use flax::{name, Entity};
+use futures::Future;
+use parking_lot::Mutex;
+
+use crate::{
+    components::ordered_children, effect::TaskHandle, signal::Mutable, time, Scope, Widget,
+};
+
+/// Shows a loading widget until a fallible future resolves, and then either the loaded widget or
+/// an error widget.
+///
+/// Unlike [`AsyncWidget`](super::AsyncWidget), something is shown right away and failures are
+/// represented. The future is created by `load` so that it can be retried, either automatically
+/// using [`Self::with_retry`] or through the [`Retry`] handle passed to the error widget.
+pub struct Suspense<F, L, E> {
+    load: F,
+    loading: L,
+    error: E,
+    retries: usize,
+    retry_delay: Duration,
+}
+
+impl<F, L, E> Suspense<F, L, E> {
+    pub fn new(load: F, loading: L, error: E) -> Self {
+        Self {
+            load,
+            loading,
+            error,
+            retries: 0,
+            retry_delay: Duration::ZERO,
+        }
+    }
+
+    /// Retries a failed load up to `retries` times, waiting `delay` before each attempt.
+    ///
+    /// The loading widget is kept until the last attempt has failed.
+    pub fn with_retry(mut self, retries: usize, delay: Duration) -> Self {
+        self.retries = retries;
+        self.retry_delay = delay;
+        self
+    }
+}
+
+/// Loads a [`Suspense`] again, showing the loading widget until the new attempt completes.
+///
+/// Passed to the error widget, for example to retry on a button press.
+#[derive(Clone)]
+pub struct Retry {
+    requests: Mutable<usize>,
+}
+
+impl Retry {
+    pub fn retry(&self) {
+        self.requests.update(|v| *v += 1);
+    }
+}
+
+impl std::fmt::Debug for Retry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Retry").finish_non_exhaustive()
+    }
+}
+
+struct State<F, L, E> {
+    load: F,
+    loading: L,
+    error: E,
+    /// The currently shown widget
+    current: Option<Entity>,
+    /// The load in flight, aborted when it is superseded or the widget is unmounted
+    task: Arc<Mutex<Option<TaskHandle>>>,
+}
+
+impl<F, L, E> State<F, L, E> {
+    fn show(&mut self, scope: &mut Scope, id: Entity) {
+        if let Some(prev) = self.current.replace(id) {
+            scope.detach(prev);
+        }
+
+        // Forget the detached widget
+        scope.set(ordered_children(), vec![id]);
+    }
+}
+
+impl<F, Fut, W, Err, L, WL, E, WE> Widget for Suspense<F, L, E>
+where
+    F: 'static + FnMut() -> Fut,
+    Fut: 'static + Future<Output = Result<W, Err>>,
+    W: Widget,
+    Err: 'static,
+    L: 'static + FnMut() -> WL,
+    WL: Widget,
+    E: 'static + FnMut(Err, Retry) -> WE,
+    WE: Widget,
+{
+    fn mount(self, scope: &mut Scope) {
+        let retries = self.retries;
+        let delay = self.retry_delay;
+
+        let retry = Retry {
+            requests: Mutable::new(0),
+        };
+
+        let state = Rc::new(RefCell::new(State {
+            load: self.load,
+            loading: self.loading,
+            error: self.error,
+            current: None,
+            task: Default::default(),
+        }));
+
+        let task = state.borrow().task.clone();
+        scope.on_cleanup(move || {
+            if let Some(task) = task.lock().take() {
+                task.abort();
+            }
+        });
+
+        scope.use_signal(retry.requests.signal(), move |scope, _| {
+            {
+                let mut state = state.borrow_mut();
+
+                // Supersede the load in flight rather than letting both run to completion
+                if let Some(prev) = state.task.lock().take() {
+                    prev.abort();
+                }
+
+                let loading = scope.attach((state.loading)());
+                state.show(scope, loading);
+            }
+
+            let future = {
+                let state = state.clone();
+                async move {
+                    let mut attempt = 0;
+                    loop {
+                        // Do not hold the borrow while loading
+                        let load = (state.borrow_mut().load)();
+                        match load.await {
+                            Err(_) if attempt < retries => {
+                                attempt += 1;
+                                tracing::debug!(attempt, "Load failed, retrying");
+                                time::sleep(delay).await;
+                            }
+                            result => return result,
+                        }
+                    }
+                }
+            };
+
+            let handle = scope.spawn_async(future, {
+                let state = state.clone();
+                let retry = retry.clone();
+                move |scope, result| {
+                    let mut state = state.borrow_mut();
+                    let id = match result {
+                        Ok(widget) => scope.attach(widget),
+                        Err(err) => scope.attach((state.error)(err, retry.clone())),
+                    };
+
+                    state.show(scope, id);
+                }
+            });
+
+            *state.borrow().task.lock() = Some(handle);
+        });
+
+        scope.set(name(), tynm::type_name::<Self>());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use crate::testing::{
+        fixtures::{labels, Label},
+        TestApp,
+    };
+
+    use super::*;
+
+    /// Fails the first `failures` attempts
+    fn flaky(
+        failures: usize,
+        attempts: &Rc<Cell<usize>>,
+    ) -> impl FnMut() -> std::future::Ready<Result<Label, &'static str>> {
+        let attempts = attempts.clone();
+        move || {
+            attempts.set(attempts.get() + 1);
+            if attempts.get() <= failures {
+                std::future::ready(Err("error"))
+            } else {
+                std::future::ready(Ok(Label("loaded")))
+            }
+        }
+    }
+
+    #[test]
+    fn suspense_ok() {
+        let mut app = TestApp::new();
+        let id = app.mount(Suspense::new(
+            || async {
+                time::sleep(Duration::from_secs(1)).await;
+                Ok::<_, &str>(Label("loaded"))
+            },
+            || Label("loading"),
+            |err, _| Label(err),
+        ));
+
+        assert_eq!(labels(&app, id), ["loading"]);
+        let [loading] = app.children(id)[..] else {
+            panic!("Expected one child")
+        };
+
+        app.advance(Duration::from_secs(1));
+        assert_eq!(labels(&app, id), ["loaded"]);
+        assert!(!app.world().is_alive(loading));
+    }
+
+    #[test]
+    fn suspense_err() {
+        let mut app = TestApp::new();
+        let id = app.mount(Suspense::new(
+            || async {
+                time::sleep(Duration::from_secs(1)).await;
+                Err::<Label, _>("error")
+            },
+            || Label("loading"),
+            |err, _| Label(err),
+        ));
+
+        assert_eq!(labels(&app, id), ["loading"]);
+
+        app.advance(Duration::from_secs(1));
+        assert_eq!(labels(&app, id), ["error"]);
+        assert_eq!(app.find_by_name(&tynm::type_name::<Label>()).len(), 1);
+    }
+
+    #[test]
+    fn suspense_with_retry() {
+        let mut app = TestApp::new();
+        let attempts = Rc::new(Cell::new(0));
+        let id = app.mount(
+            Suspense::new(
+                flaky(2, &attempts),
+                || Label("loading"),
+                |err, _| Label(err),
+            )
+            .with_retry(2, Duration::from_secs(1)),
+        );
+
+        // The first attempt failed, and the next waits for the delay
+        assert_eq!(attempts.get(), 1);
+        assert_eq!(labels(&app, id), ["loading"]);
+
+        app.advance(Duration::from_millis(999));
+        assert_eq!(attempts.get(), 1);
+
+        app.advance(Duration::from_millis(1));
+        assert_eq!(attempts.get(), 2);
+        assert_eq!(labels(&app, id), ["loading"]);
+
+        app.advance(Duration::from_secs(1));
+        assert_eq!(attempts.get(), 3);
+        assert_eq!(labels(&app, id), ["loaded"]);
+
+        // Out of retries
+        let attempts = Rc::new(Cell::new(0));
+        let id = app.mount(
+            Suspense::new(
+                flaky(3, &attempts),
+                || Label("loading"),
+                |err, _| Label(err),
+            )
+            .with_retry(2, Duration::from_secs(1)),
+        );
+
+        app.advance(Duration::from_secs(1));
+        assert_eq!(labels(&app, id), ["loading"]);
+
+        app.advance(Duration::from_secs(1));
+        assert_eq!(attempts.get(), 3);
+        assert_eq!(labels(&app, id), ["error"]);
+    }
+
+    #[test]
+    fn suspense_retry_handle() {
+        let mut app = TestApp::new();
+        let attempts = Rc::new(Cell::new(0));
+        let handle = Rc::new(RefCell::new(None));
+
+        let id = app.mount(Suspense::new(flaky(1, &attempts), || Label("loading"), {
+            let handle = handle.clone();
+            move |err, retry| {
+                *handle.borrow_mut() = Some(retry);
+                Label(err)
+            }
+        }));
+
+        assert_eq!(attempts.get(), 1);
+        assert_eq!(labels(&app, id), ["error"]);
+        let [error] = app.children(id)[..] else {
+            panic!("Expected one child")
+        };
+
+        handle.borrow().as_ref().unwrap().retry();
+        app.run_until_stalled();
+
+        assert_eq!(attempts.get(), 2);
+        assert_eq!(labels(&app, id), ["loaded"]);
+        assert!(!app.world().is_alive(error));
+        assert_eq!(app.find_by_name(&tynm::type_name::<Label>()).len(), 1);
+    }
+
+    #[test]
+    fn suspense_retry_while_loading() {
+        let mut app = TestApp::new();
+        let attempts = Rc::new(Cell::new(0));
+        let handle = Rc::new(RefCell::new(None));
+
+        let load = {
+            let attempts = attempts.clone();
+            move || {
+                attempts.set(attempts.get() + 1);
+                async {
+                    time::sleep(Duration::from_secs(1)).await;
+                    Err::<Label, _>("error")
+                }
+            }
+        };
+
+        let id = app.mount(
+            Suspense::new(load, || Label("loading"), {
+                let handle = handle.clone();
+                move |err, retry| {
+                    *handle.borrow_mut() = Some(retry);
+                    Label(err)
+                }
+            })
+            .with_retry(1, Duration::from_secs(1)),
+        );
+
+        for _ in 0..3 {
+            app.advance(Duration::from_secs(1));
+        }
+
+        assert_eq!(attempts.get(), 2);
+        assert_eq!(labels(&app, id), ["error"]);
+
+        let retry = handle.borrow().clone().unwrap();
+        retry.retry();
+        app.run_until_stalled();
+        assert_eq!(attempts.get(), 3);
+
+        // The load failed once and waits for the delay before its next attempt
+        app.advance(Duration::from_secs(1));
+        app.advance(Duration::from_millis(500));
+        assert_eq!(labels(&app, id), ["loading"]);
+
+        retry.retry();
+        app.run_until_stalled();
+        assert_eq!(attempts.get(), 4);
+
+        // The superseded load does not make its next attempt
+        app.advance(Duration::from_millis(500));
+        assert_eq!(attempts.get(), 4);
+        assert_eq!(labels(&app, id), ["loading"]);
+
+        // Only the new load fails, retries and shows the error
+        app.advance(Duration::from_millis(500));
+        app.advance(Duration::from_secs(1));
+        assert_eq!(attempts.get(), 5);
+        assert_eq!(labels(&app, id), ["loading"]);
+
+        app.advance(Duration::from_secs(1));
+        assert_eq!(attempts.get(), 5);
+        assert_eq!(labels(&app, id), ["error"]);
+    }
+}
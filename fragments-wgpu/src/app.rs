@@ -1,11 +1,14 @@
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use flax::{
     component, BoxedSystem, Debuggable, Entity, EntityBorrow, Mutable, Query, QueryBorrow,
     Schedule, System, World,
 };
 use fragments_core::{
-    effect::Executor,
+    effect::{Executor, Phase},
     events::EventRegistry,
     frame::Frame,
     layout::{
@@ -13,13 +16,16 @@ use fragments_core::{
         systems::{update_layout_system, update_transform_system},
         Direction, Layout,
     },
+    time::{self, Timers},
     Widget,
 };
+use futures::task::ArcWake;
 use glam::{vec2, Mat4, Vec2};
+use parking_lot::Mutex;
 use winit::{
     dpi::PhysicalSize,
     event::Event,
-    event_loop::{ControlFlow, EventLoop},
+    event_loop::{ControlFlow, EventLoopBuilder, EventLoopProxy},
     window::WindowBuilder,
 };
 
@@ -30,15 +36,49 @@ use crate::{
     renderer::Renderer,
 };
 
-pub struct AppBuilder {}
+/// The target duration of a frame. Idle tasks only run if the frame finished earlier.
+const FRAME_TIME: Duration = Duration::from_micros(16_667);
+/// The time spent polling tasks each frame before the rest is deferred to the next frame
+const TASK_BUDGET: Duration = Duration::from_millis(8);
+
+pub struct AppBuilder {
+    sleep_when_idle: bool,
+    loop_timers: bool,
+}
 
 impl AppBuilder {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            sleep_when_idle: false,
+            loop_timers: false,
+        }
+    }
+
+    /// Sleep until the next window event, task wakeup or timer rather than redrawing
+    /// continuously.
+    ///
+    /// Drastically reduces the idle cpu usage, but [`RedrawEvent`] is only emitted for the frames
+    /// which are woken. Running animations keep waking the loop until they finish.
+    pub fn sleep_when_idle(mut self, enabled: bool) -> Self {
+        self.sleep_when_idle = enabled;
+        self
+    }
+
+    /// Drive the timers from the event loop rather than the background thread of
+    /// [`GLOBAL_TIMER`](fragments_core::time::GLOBAL_TIMER).
+    ///
+    /// Timers then fire at the start of the frame rather than as soon as they are due, and
+    /// together with [`Self::sleep_when_idle`] the loop sleeps until the next timer.
+    pub fn loop_timers(mut self, enabled: bool) -> Self {
+        self.loop_timers = enabled;
+        self
     }
 
     pub fn build(self) -> App {
-        App {}
+        App {
+            sleep_when_idle: self.sleep_when_idle,
+            loop_timers: self.loop_timers,
+        }
     }
 }
 
@@ -78,13 +118,44 @@ where
     }
 }
 
-pub struct App {}
+/// Sent to wake the event loop
+enum AppEvent {
+    Wake,
+}
+
+struct LoopWaker(Mutex<EventLoopProxy<AppEvent>>);
+
+impl ArcWake for LoopWaker {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        // Fails if the loop has already exited
+        let _ = arc_self.0.lock().send_event(AppEvent::Wake);
+    }
+}
+
+pub struct App {
+    sleep_when_idle: bool,
+    loop_timers: bool,
+}
 
 impl App {
     /// Opens a window and enters the main event loop
     pub fn run(self, root: impl Widget + 'static) -> anyhow::Result<()> {
+        let event_loop = EventLoopBuilder::<AppEvent>::with_user_event().build();
+        let waker =
+            futures::task::waker(Arc::new(LoopWaker(Mutex::new(event_loop.create_proxy()))));
+
         // Create a new executor capable of executing the tasks in the UI
         let mut executor = Executor::new();
+        executor.set_waker(waker.clone());
+
+        let mut timers = self.loop_timers.then(|| {
+            let mut timers = Timers::new();
+            timers.set_waker(waker);
+            executor.set_timers(timers.handle());
+            timers
+        });
+
+        let _timers_guard = timers.as_ref().map(|timers| time::enter(timers.handle()));
 
         // Contains the state
         let mut frame = Frame::new(
@@ -93,7 +164,6 @@ impl App {
             Arc::new(EventRegistry::new()),
         );
 
-        let event_loop = EventLoop::new();
         let window = WindowBuilder::new().build(&event_loop)?;
         let window_size = window.inner_size();
 
@@ -128,19 +198,68 @@ impl App {
             .with_system(resize_cameras_system())
             .with_system(resize_renderer_system());
 
-        let mut on_frame = Schedule::new()
+        let mut on_layout = Schedule::new()
             .with_system(update_layout_system())
-            .with_system(update_transform_system())
-            .with_system(draw_system());
+            .with_system(update_transform_system());
+
+        let mut on_draw = Schedule::new().with_system(draw_system());
+
+        let sleep_when_idle = self.sleep_when_idle;
 
         event_loop.run(move |event, _, ctl| match event {
             Event::MainEventsCleared => {
+                let frame_start = Instant::now();
+                if let Some(timers) = &mut timers {
+                    timers.tick(frame_start);
+                }
+
+                let deadline = frame_start + TASK_BUDGET;
+
                 // Update the UI
+                frame.observe_changes();
                 events.emit(&mut frame, &RedrawEvent);
-                executor.update(&mut frame);
-                if let Err(err) = on_frame.execute_seq(&mut frame.world) {
+                let mut stats = executor.update_phase_until(Phase::PreLayout, deadline, &mut frame);
+
+                if let Err(err) = on_layout.execute_seq(&mut frame.world) {
+                    tracing::error!("Error updating layout: {:?}", err);
+                }
+
+                frame.observe_changes();
+
+                stats += executor.update_phase_until(Phase::PostLayout, deadline, &mut frame);
+                stats += executor.update_phase_until(Phase::PreRender, deadline, &mut frame);
+
+                if let Err(err) = on_draw.execute_seq(&mut frame.world) {
                     tracing::error!("Error drawing: {:?}", err);
                 }
+
+                if frame_start.elapsed() < FRAME_TIME {
+                    stats += executor.update_phase_until(
+                        Phase::Idle,
+                        frame_start + FRAME_TIME,
+                        &mut frame,
+                    );
+                } else {
+                    // Carry the idle tasks over to the next frame
+                    stats += executor.skip_phase(Phase::Idle);
+                }
+
+                // Notify the changes made by tasks, which wakes the observing tasks for the next
+                // frame
+                frame.observe_changes();
+
+                if stats.deferred > 0 {
+                    tracing::debug!(?stats, "Deferred tasks to the next frame");
+                }
+
+                if sleep_when_idle && !matches!(*ctl, ControlFlow::ExitWithCode(_)) {
+                    // Sleep until the next timer, or until woken by an event or a task. Timers
+                    // registered meanwhile wake the loop.
+                    *ctl = match timers.as_ref().and_then(Timers::next_deadline) {
+                        Some(deadline) => ControlFlow::WaitUntil(deadline),
+                        None => ControlFlow::Wait,
+                    };
+                }
             }
             Event::WindowEvent { event, .. } => match event {
                 winit::event::WindowEvent::CloseRequested => {
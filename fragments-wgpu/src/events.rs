@@ -1,8 +1,8 @@
 use flax::component;
+pub use fragments_core::events::RedrawEvent;
 use fragments_core::{context, events::EventHandler};
 use winit::{dpi::PhysicalSize, event::KeyboardInput};
 
-pub struct RedrawEvent;
 pub struct ResizeEvent(pub PhysicalSize<u32>);
 
 context! {